@@ -0,0 +1,351 @@
+use crate::peso::Peso;
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+// Árboles generadores mínimos, para comparar con el árbol de caminos mínimos de Dijkstra: el de
+// Dijkstra minimiza la distancia del origen a cada nodo, estos el peso total de las aristas.
+// - Prim y Kruskal leen el grafo como no dirigido (cada arista sirve en los dos sentidos). Si no
+//   es conexo dan un bosque, un árbol por componente.
+// - Chu–Liu/Edmonds respeta las direcciones: la arborescencia mínima desde una raíz, donde cada
+//   nodo tiene exactamente una arista de entrada. Elige la entrada más barata de cada nodo y si
+//   eso forma ciclos los contrae en un solo nodo (repesando las aristas que entran al ciclo) y
+//   repite; al volver, la arista que entra al ciclo reemplaza la del ciclo que llegaba a ese nodo.
+
+/// Árbol (o bosque) generador mínimo con Prim, leyendo las aristas sin dirección.
+///
+/// Devuelve `(aristas, total, log)` con los índices de las aristas del árbol. Acepta pesos
+/// negativos. Devuelve `Err` si el total se desborda.
+pub fn prim<W: Peso>(g: &DiGraph<String, W>) -> Result<(Vec<usize>, W, Vec<String>), String> {
+    let n = g.node_count();
+    let mut ady: Vec<Vec<(usize, usize)>> = vec![vec![]; n];
+    for e in g.edge_references() {
+        let (u, v, id) = (e.source().index(), e.target().index(), e.id().index());
+        ady[u].push((v, id));
+        ady[v].push((u, id));
+    }
+    let mut en_arbol = vec![false; n];
+    let mut aristas = vec![];
+    let mut total = W::cero();
+    let mut log = vec![];
+
+    for inicio in 0..n {
+        if en_arbol[inicio] {
+            continue;
+        }
+        log.push(format!("Empiezo un árbol en {}", inicio));
+        en_arbol[inicio] = true;
+        let mut heap: BinaryHeap<Reverse<(W, usize, usize)>> = BinaryHeap::new();
+        for &(v, id) in &ady[inicio] {
+            heap.push(Reverse((g.raw_edges()[id].weight, id, v)));
+        }
+        while let Some(Reverse((w, id, v))) = heap.pop() {
+            if en_arbol[v] {
+                continue;
+            }
+            en_arbol[v] = true;
+            aristas.push(id);
+            total = total
+                .sumar(w)
+                .ok_or_else(|| format!("Desborde: total {} + {}", total, w))?;
+            let e = &g.raw_edges()[id];
+            log.push(format!(
+                "  Agrego {} - {} (w={}), llega a {}",
+                e.source().index(),
+                e.target().index(),
+                w,
+                v
+            ));
+            for &(x, id) in &ady[v] {
+                if !en_arbol[x] {
+                    heap.push(Reverse((g.raw_edges()[id].weight, id, x)));
+                }
+            }
+        }
+    }
+    log.push(format!(
+        "Fin: {} aristas, peso total {}",
+        aristas.len(),
+        total
+    ));
+    Ok((aristas, total, log))
+}
+
+/// Árbol (o bosque) generador mínimo con Kruskal, leyendo las aristas sin dirección.
+///
+/// Devuelve lo mismo que [`prim`]; con empates el árbol puede ser otro, pero el total es el mismo.
+pub fn kruskal<W: Peso>(g: &DiGraph<String, W>) -> Result<(Vec<usize>, W, Vec<String>), String> {
+    let n = g.node_count();
+    let mut orden: Vec<usize> = (0..g.edge_count()).collect();
+    orden.sort_by_key(|&id| g.raw_edges()[id].weight);
+    // union-find con compresión de caminos
+    let mut padre: Vec<usize> = (0..n).collect();
+    fn raiz(padre: &mut [usize], mut x: usize) -> usize {
+        while padre[x] != x {
+            padre[x] = padre[padre[x]];
+            x = padre[x];
+        }
+        x
+    }
+    let mut aristas = vec![];
+    let mut total = W::cero();
+    let mut log = vec![];
+
+    for id in orden {
+        let e = &g.raw_edges()[id];
+        let (u, v, w) = (e.source().index(), e.target().index(), e.weight);
+        let (ru, rv) = (raiz(&mut padre, u), raiz(&mut padre, v));
+        if ru == rv {
+            log.push(format!(
+                "  Descarto {} - {} (w={}), cerraría un ciclo",
+                u, v, w
+            ));
+            continue;
+        }
+        padre[ru] = rv;
+        aristas.push(id);
+        total = total
+            .sumar(w)
+            .ok_or_else(|| format!("Desborde: total {} + {}", total, w))?;
+        log.push(format!("Agrego {} - {} (w={})", u, v, w));
+    }
+    log.push(format!(
+        "Fin: {} aristas, peso total {}",
+        aristas.len(),
+        total
+    ));
+    Ok((aristas, total, log))
+}
+
+/// Arborescencia mínima desde `raiz` con Chu–Liu/Edmonds: un camino de la raíz a cada nodo
+/// alcanzable, respetando las direcciones, con el menor peso total. Los nodos que no se alcanzan
+/// desde la raíz quedan afuera.
+///
+/// Devuelve `(aristas, total, log)` como [`prim`]. Acepta pesos negativos. Devuelve `Err` si un
+/// peso repesado o el total se desbordan.
+pub fn arborescencia_minima<W: Peso>(
+    g: &DiGraph<String, W>,
+    raiz: usize,
+) -> Result<(Vec<usize>, W, Vec<String>), String> {
+    // solo los alcanzables, renumerados 0..k con la raíz en 0
+    let n = g.node_count();
+    let mut indice: Vec<Option<usize>> = vec![None; n];
+    indice[raiz] = Some(0);
+    let mut k = 1;
+    let mut cola = VecDeque::from([raiz]);
+    while let Some(u) = cola.pop_front() {
+        for e in g.edges(petgraph::prelude::NodeIndex::new(u)) {
+            let v = e.target().index();
+            if indice[v].is_none() {
+                indice[v] = Some(k);
+                k += 1;
+                cola.push_back(v);
+            }
+        }
+    }
+    let mut aristas: Vec<(usize, usize, W)> = vec![];
+    let mut ids = vec![];
+    for e in g.edge_references() {
+        if let (Some(u), Some(v)) = (indice[e.source().index()], indice[e.target().index()])
+            && u != v
+        {
+            aristas.push((u, v, *e.weight()));
+            ids.push(e.id().index());
+        }
+    }
+
+    let mut nombres = vec![String::new(); k];
+    for (v, i) in indice.iter().enumerate() {
+        if let Some(i) = *i {
+            nombres[i] = v.to_string();
+        }
+    }
+    let mut log = vec![format!("{} nodos alcanzables desde {}", k, raiz)];
+    let elegidas = edmonds(&nombres, &aristas, &mut log)?;
+    let mut total = W::cero();
+    let mut resultado = vec![];
+    for i in elegidas {
+        let w = aristas[i].2;
+        total = total
+            .sumar(w)
+            .ok_or_else(|| format!("Desborde: total {} + {}", total, w))?;
+        resultado.push(ids[i]);
+    }
+    resultado.sort_unstable();
+    log.push(format!(
+        "Fin: {} aristas, peso total {}",
+        resultado.len(),
+        total
+    ));
+    Ok((resultado, total, log))
+}
+
+// Un nivel de Chu–Liu/Edmonds sobre los nodos 0..n con raíz 0, todos alcanzables; `nombres` es
+// cómo se llama cada uno en el log (un ciclo contraído es `[a b c]`). Devuelve los índices en
+// `aristas` de las elegidas
+fn edmonds<W: Peso>(
+    nombres: &[String],
+    aristas: &[(usize, usize, W)],
+    log: &mut Vec<String>,
+) -> Result<Vec<usize>, String> {
+    let n = nombres.len();
+    // la entrada más barata de cada nodo
+    let mut entrada: Vec<Option<usize>> = vec![None; n];
+    for (i, &(_, v, w)) in aristas.iter().enumerate() {
+        if v != 0 && entrada[v].is_none_or(|j| w < aristas[j].2) {
+            entrada[v] = Some(i);
+        }
+    }
+
+    // ciclos entre las entradas elegidas: se sigue hacia atrás desde cada nodo
+    let mut ciclo_de: Vec<Option<usize>> = vec![None; n];
+    let mut ciclos: Vec<Vec<usize>> = vec![];
+    let mut marca = vec![usize::MAX; n];
+    for inicio in 1..n {
+        let mut v = inicio;
+        while v != 0 && marca[v] == usize::MAX && ciclo_de[v].is_none() {
+            marca[v] = inicio;
+            v = aristas[entrada[v].unwrap()].0;
+        }
+        // se volvió a un nodo de este mismo recorrido: hay ciclo
+        if v != 0 && marca[v] == inicio && ciclo_de[v].is_none() {
+            let mut ciclo = vec![v];
+            let mut x = aristas[entrada[v].unwrap()].0;
+            while x != v {
+                ciclo.push(x);
+                x = aristas[entrada[x].unwrap()].0;
+            }
+            for &x in &ciclo {
+                ciclo_de[x] = Some(ciclos.len());
+            }
+            ciclos.push(ciclo);
+        }
+    }
+    if ciclos.is_empty() {
+        return Ok(entrada.iter().flatten().copied().collect());
+    }
+
+    // contraer: cada ciclo es un nodo nuevo, el resto se renumera, la raíz sigue en 0
+    let mut nuevo = vec![0; n];
+    let mut m = 0;
+    for v in 0..n {
+        if ciclo_de[v].is_none() {
+            nuevo[v] = m;
+            m += 1;
+        }
+    }
+    for v in 0..n {
+        if let Some(c) = ciclo_de[v] {
+            nuevo[v] = m + c;
+        }
+    }
+    let mut nombres_nuevos = vec![String::new(); m + ciclos.len()];
+    for v in 0..n {
+        if ciclo_de[v].is_none() {
+            nombres_nuevos[nuevo[v]] = nombres[v].clone();
+        }
+    }
+    for (c, ciclo) in ciclos.iter().enumerate() {
+        let en_orden: Vec<&str> = ciclo.iter().rev().map(|&x| nombres[x].as_str()).collect();
+        log.push(format!(
+            "Contraigo el ciclo {} -> {}",
+            en_orden.join(" -> "),
+            en_orden[0]
+        ));
+        nombres_nuevos[m + c] = format!("[{}]", en_orden.join(" "));
+    }
+    let mut contraidas: Vec<(usize, usize, W)> = vec![];
+    let mut origen_de: Vec<usize> = vec![];
+    for (i, &(u, v, w)) in aristas.iter().enumerate() {
+        if nuevo[u] == nuevo[v] {
+            continue;
+        }
+        // entrar al ciclo por v ahorra la entrada que v tenía dentro del ciclo
+        let w = match ciclo_de[v] {
+            Some(_) => {
+                let dentro = aristas[entrada[v].unwrap()].2;
+                w.restar(dentro)
+                    .ok_or_else(|| format!("Desborde: peso repesado {} - {}", w, dentro))?
+            }
+            None => w,
+        };
+        contraidas.push((nuevo[u], nuevo[v], w));
+        origen_de.push(i);
+    }
+
+    let elegidas_contraidas = edmonds(&nombres_nuevos, &contraidas, log)?;
+    let mut elegidas = vec![];
+    let mut entra_por: Vec<Option<usize>> = vec![None; ciclos.len()];
+    for j in elegidas_contraidas {
+        let i = origen_de[j];
+        elegidas.push(i);
+        if let Some(c) = ciclo_de[aristas[i].1] {
+            entra_por[c] = Some(aristas[i].1);
+        }
+    }
+    // cada ciclo se abre donde entra la arista elegida
+    for (c, ciclo) in ciclos.iter().enumerate() {
+        for &x in ciclo {
+            if Some(x) != entra_por[c] {
+                elegidas.push(entrada[x].unwrap());
+            }
+        }
+    }
+    Ok(elegidas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grafo::de_texto;
+
+    fn ordenadas(mut aristas: Vec<usize>) -> Vec<usize> {
+        aristas.sort();
+        aristas
+    }
+
+    // B A 1 es paralela (al revés) de A B 4; E F es otra componente
+    const BOSQUE: &str = "nodos: A B C D E F\nA B 4\nB C 1\nA C 3\nC D 2\nD B 5\nB A 1\nE F 7";
+
+    #[test]
+    fn prim_y_kruskal_dan_el_mismo_bosque() {
+        let g = de_texto::<i64>(BOSQUE);
+        let (aristas, total, _) = prim(&g).unwrap();
+        assert_eq!(total, 11);
+        assert_eq!(ordenadas(aristas), vec![1, 3, 5, 6]);
+        let (aristas, total, _) = kruskal(&g).unwrap();
+        assert_eq!(total, 11);
+        assert_eq!(ordenadas(aristas), vec![1, 3, 5, 6]);
+    }
+
+    #[test]
+    fn edmonds_contrae_el_ciclo() {
+        // las entradas más baratas de A y B forman el ciclo A <-> B; al contraerlo conviene
+        // entrar por R -> A (5 - 1) antes que por R -> B (6 - 1). X no se alcanza desde R
+        let g =
+            de_texto::<i64>("nodos: R A B C X\nR A 5\nR B 6\nA B 1\nB A 1\nA C 2\nB C 9\nX R 1");
+        let (aristas, total, _) = arborescencia_minima(&g, 0).unwrap();
+        assert_eq!(total, 8);
+        assert_eq!(ordenadas(aristas), vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn edmonds_respeta_las_direcciones() {
+        // sin dirección lo mínimo sería A - B y B - C (2), pero a C solo se llega desde A
+        let g = de_texto::<i64>("A B 1\nC B 1\nA C 5");
+        let (_, total, _) = prim(&g).unwrap();
+        assert_eq!(total, 2);
+        let (aristas, total, _) = arborescencia_minima(&g, 0).unwrap();
+        assert_eq!(total, 6);
+        assert_eq!(ordenadas(aristas), vec![0, 2]);
+    }
+
+    #[test]
+    fn raiz_sola() {
+        let g = de_texto::<i64>("nodos: A B\nB A 1");
+        let (aristas, total, _) = arborescencia_minima(&g, 0).unwrap();
+        assert!(aristas.is_empty());
+        assert_eq!(total, 0);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::dijkstra::{CaminosMinimos, dijkstra_detallado, iter_caminos};
+use crate::peso::Peso;
+use petgraph::graph::{DiGraph, EdgeIndex, NodeIndex};
+use petgraph::visit::EdgeRef;
+
+// Los `preds` de Dijkstra dicen de qué nodos se llega a cada uno, pero no por cuál arista (con
+// paralelas puede haber varias) ni dejan recorrer el resultado como grafo. Aquí se guardan las
+// aristas "justas" que llegan a cada nodo (dist[u] + w == dist[v]), que forman el DAG de caminos
+// mínimos, y de esas la primera de cada nodo forma el árbol: la del primer predecesor en `preds`
+// y, entre paralelas, la de menor índice. Seguir el árbol da el mismo camino que el primero de
+// `iter_caminos`.
+
+/// Resultado de Dijkstra como árbol y DAG de caminos mínimos desde un origen.
+#[derive(Clone, Debug)]
+pub struct ArbolCaminos<W> {
+    /// Distancia mínima a cada nodo, `None` si no es alcanzable.
+    pub dist: Vec<Option<W>>,
+    /// Traza del algoritmo.
+    pub log: Vec<String>,
+    origen: usize,
+    preds: Vec<Vec<usize>>,
+    // (u, índice de la arista) de cada arista justa u -> v que llega a v; la primera es la del árbol
+    entrantes: Vec<Vec<(usize, usize)>>,
+}
+
+impl<W: Peso> ArbolCaminos<W> {
+    /// Arma el árbol a partir de lo que devuelve [`dijkstra_detallado`] o cualquiera de los
+    /// solvers con el mismo formato `(dist, preds, log)` (A*, Bellman-Ford, las variantes con
+    /// opciones), corridos sobre `g` desde `origen`.
+    pub fn new(
+        g: &DiGraph<String, W>,
+        origen: usize,
+        dist: Vec<Option<W>>,
+        preds: Vec<Vec<usize>>,
+        log: Vec<String>,
+    ) -> Self {
+        let mut entrantes: Vec<Vec<(usize, usize)>> = vec![vec![]; g.node_count()];
+        for e in g.edge_references() {
+            let (u, v) = (e.source().index(), e.target().index());
+            // con u en preds[v] los dos son alcanzables
+            if preds[v].contains(&u) && dist[u].and_then(|d| d.sumar(*e.weight())) == dist[v] {
+                entrantes[v].push((u, e.id().index()));
+            }
+        }
+        // en el orden de preds y, entre paralelas, por índice
+        for (v, lista) in entrantes.iter_mut().enumerate() {
+            lista.sort_by_key(|&(u, id)| (preds[v].iter().position(|&p| p == u), id));
+        }
+        ArbolCaminos {
+            dist,
+            log,
+            origen,
+            preds,
+            entrantes,
+        }
+    }
+
+    /// El nodo desde el que se calcularon las distancias.
+    pub fn origen(&self) -> usize {
+        self.origen
+    }
+
+    /// Distancia mínima de `origen` a `v`, `None` si no es alcanzable.
+    pub fn distancia(&self, v: usize) -> Option<W> {
+        self.dist[v]
+    }
+
+    /// Si hay algún camino de `origen` a `v`.
+    pub fn es_alcanzable(&self, v: usize) -> bool {
+        self.dist[v].is_some()
+    }
+
+    /// El camino mínimo de `origen` a `v` por el árbol, `None` si no es alcanzable.
+    pub fn camino_a(&self, v: usize) -> Option<Vec<usize>> {
+        self.dist[v]?;
+        let mut camino = vec![v];
+        let mut x = v;
+        while let Some(&(u, _)) = self.entrantes[x].first() {
+            camino.push(u);
+            x = u;
+        }
+        camino.reverse();
+        Some(camino)
+    }
+
+    /// Todos los caminos mínimos empatados de `origen` a `v`, como [`iter_caminos`]. Vacío si no
+    /// es alcanzable.
+    pub fn caminos_a(&self, v: usize) -> CaminosMinimos<'_> {
+        iter_caminos(&self.preds, self.origen, v)
+    }
+
+    /// Los predecesores empatados de cada nodo, el mismo `preds` de [`dijkstra_detallado`].
+    pub fn preds(&self) -> &[Vec<usize>] {
+        &self.preds
+    }
+
+    /// Las aristas del árbol como `(u, v, índice de la arista)`, una por cada nodo alcanzable
+    /// salvo el origen, en orden de `v`.
+    pub fn aristas_arbol(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        self.entrantes
+            .iter()
+            .enumerate()
+            .filter_map(|(v, lista)| lista.first().map(|&(u, id)| (u, v, id)))
+    }
+
+    /// Las aristas del DAG de caminos mínimos (todas las que están en algún camino mínimo desde
+    /// el origen) como `(u, v, índice de la arista)`, en orden de `v`.
+    pub fn aristas_dag(&self) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+        self.entrantes
+            .iter()
+            .enumerate()
+            .flat_map(|(v, lista)| lista.iter().map(move |&(u, id)| (u, v, id)))
+    }
+
+    /// El árbol como grafo de petgraph: los mismos nodos que `g` (con los mismos índices) y solo
+    /// las aristas del árbol, con su peso. `g` tiene que ser el grafo sobre el que se corrió.
+    pub fn arbol_como_grafo(&self, g: &DiGraph<String, W>) -> DiGraph<String, W> {
+        como_grafo(g, self.aristas_arbol())
+    }
+
+    /// Igual que [`arbol_como_grafo`](Self::arbol_como_grafo) con las aristas del DAG.
+    pub fn dag_como_grafo(&self, g: &DiGraph<String, W>) -> DiGraph<String, W> {
+        como_grafo(g, self.aristas_dag())
+    }
+}
+
+/// Dijkstra desde `origen` con el resultado como [`ArbolCaminos`].
+///
+/// Devuelve los mismos errores que [`dijkstra_detallado`].
+pub fn arbol_caminos_minimos<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+) -> Result<ArbolCaminos<W>, String> {
+    let (dist, preds, log) = dijkstra_detallado(g, origen)?;
+    Ok(ArbolCaminos::new(g, origen, dist, preds, log))
+}
+
+fn como_grafo<W: Peso>(
+    g: &DiGraph<String, W>,
+    aristas: impl Iterator<Item = (usize, usize, usize)>,
+) -> DiGraph<String, W> {
+    let mut h = DiGraph::<String, W>::new();
+    for v in g.node_indices() {
+        h.add_node(g[v].clone());
+    }
+    for (u, v, id) in aristas {
+        h.add_edge(NodeIndex::new(u), NodeIndex::new(v), g[EdgeIndex::new(id)]);
+    }
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellman_ford::bellman_ford_detallado;
+    use crate::grafo::de_texto;
+
+    // dos caminos empatados a T, uno de ellos con dos aristas paralelas A -> T; X aislado
+    const DIAMANTE: &str = "nodos: O A B T X\nO A 1\nO B 1\nA T 1\nB T 1\nA T 1\nO T 5";
+
+    #[test]
+    fn arbol_y_dag_de_caminos_minimos() {
+        let g = de_texto::<i64>(DIAMANTE);
+        let a = arbol_caminos_minimos(&g, 0).unwrap();
+        assert_eq!(a.distancia(3), Some(2));
+        assert_eq!(a.camino_a(3), Some(vec![0, 2, 3]));
+        assert_eq!(a.caminos_a(3).count(), 2);
+        assert_eq!(
+            a.aristas_arbol().collect::<Vec<_>>(),
+            vec![(0, 1, 0), (0, 2, 1), (2, 3, 3)]
+        );
+        // las dos paralelas A -> T están en el DAG, O -> T no
+        assert_eq!(
+            a.aristas_dag().collect::<Vec<_>>(),
+            vec![(0, 1, 0), (0, 2, 1), (2, 3, 3), (1, 3, 2), (1, 3, 4)]
+        );
+        let arbol = a.arbol_como_grafo(&g);
+        assert_eq!((arbol.node_count(), arbol.edge_count()), (5, 3));
+        assert_eq!(a.dag_como_grafo(&g).edge_count(), 5);
+    }
+
+    #[test]
+    fn nodo_inalcanzable() {
+        let g = de_texto::<i64>(DIAMANTE);
+        let a = arbol_caminos_minimos(&g, 0).unwrap();
+        assert!(!a.es_alcanzable(4));
+        assert_eq!(a.camino_a(4), None);
+        assert_eq!(a.caminos_a(4).count(), 0);
+        assert_eq!(a.camino_a(0), Some(vec![0]));
+    }
+
+    #[test]
+    fn sirve_con_bellman_ford() {
+        let g = de_texto::<i64>("A B 4\nA C 2\nC B -3");
+        let (dist, preds, log) = bellman_ford_detallado(&g, 0).unwrap();
+        let a = ArbolCaminos::new(&g, 0, dist, preds, log);
+        assert_eq!(a.distancia(1), Some(-1));
+        assert_eq!(a.camino_a(1), Some(vec![0, 2, 1]));
+        assert_eq!(a.aristas_arbol().count(), 2);
+    }
+}
@@ -0,0 +1,269 @@
+use crate::dijkstra::{Entrada, Resultado, es_ancestro};
+use crate::grafo::Coordenadas;
+use crate::peso::Peso;
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use std::collections::BinaryHeap;
+
+// A*: como dijkstra pero la cola se ordena por f = g + h, donde h estima lo que falta hasta el
+// destino. Si h nunca se pasa del costo real (admisible) y es consistente el camino sigue siendo
+// óptimo, y mientras mejor sea h menos nodos se expanden. Con h = 0 es exactamente dijkstra.
+
+/// A* de `origen` a `destino` con la heurística `h(v)` (estimación del costo de `v` a `destino`).
+///
+/// Devuelve `(dist, preds, log)` como [`dijkstra_detallado`](crate::dijkstra::dijkstra_detallado),
+/// pero se detiene al fijar el destino: `dist[destino]` y los `preds` de los nodos en caminos
+/// óptimos son correctos (incluidos los empates), el resto puede quedar sin calcular o sin fijar.
+/// El log muestra `f = g + h` de cada nodo seleccionado.
+///
+/// La heurística debe ser admisible y consistente, por ejemplo [`euclidiana`] o [`manhattan`]
+/// con una escala que no sobreestime los pesos. Devuelve `Err` con pesos negativos o desborde.
+pub fn astar_detallado<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+    destino: usize,
+    h: impl Fn(usize) -> W,
+) -> Result<Resultado<W>, String> {
+    let n = g.node_count();
+    let mut dist: Vec<Option<W>> = vec![None; n];
+    let mut preds: Vec<Vec<usize>> = vec![vec![]; n];
+    let mut cerrado = vec![false; n];
+    let mut heap: BinaryHeap<Entrada<W>> = BinaryHeap::new();
+    let mut log: Vec<String> = vec![];
+    let mut expandidos = 0;
+
+    let f = |gv: W, v: usize| {
+        gv.sumar(h(v))
+            .ok_or_else(|| format!("Desborde calculando f = {} + h({})", gv, v))
+    };
+
+    dist[origen] = Some(W::cero());
+    let f0 = f(W::cero(), origen)?;
+    heap.push(Entrada {
+        dist: f0,
+        node: origen,
+    });
+    log.push(format!(
+        "Inicializo g[{}]=0, h={}, f={}, resto = ∞",
+        origen,
+        h(origen),
+        f0
+    ));
+
+    while let Some(Entrada { dist: f_u, node: u }) = heap.pop() {
+        // con el destino fijo solo faltan los empates: entradas con f igual al óptimo
+        if let Some(d) = dist[destino]
+            && cerrado[destino]
+            && f_u > d
+        {
+            break;
+        }
+        if cerrado[u] {
+            continue;
+        }
+        cerrado[u] = true;
+        expandidos += 1;
+        // la primera vez que sale un nodo es con su mejor g (misma h en todas sus entradas)
+        let g_u = dist[u].expect("todo nodo en la cola tiene distancia");
+        log.push(format!(
+            "Selecciono u={} con f=g+h={}+{}={}",
+            u,
+            g_u,
+            h(u),
+            f_u
+        ));
+
+        for e in g.edges(petgraph::prelude::NodeIndex::new(u)) {
+            let v = e.target().index();
+            let w = *e.weight();
+            if w < W::cero() {
+                return Err(format!(
+                    "Peso negativo en la arista {} -> {} (w={}), A* no los acepta",
+                    u, v, w
+                ));
+            }
+
+            let alt = g_u.sumar(w).ok_or_else(|| {
+                format!(
+                    "Desborde: g[{}]={} + w={} ({} -> {}) no cabe en el tipo de peso",
+                    u, g_u, w, u, v
+                )
+            })?;
+            // un nodo cerrado solo puede recibir empates; si `v` ya está en algún camino a `u`
+            // (aristas de peso 0) se ignoran para no armar ciclos en preds
+            if cerrado[v] {
+                if dist[v] == Some(alt) && !preds[v].contains(&u) && !es_ancestro(&preds, v, u) {
+                    preds[v].push(u);
+                    log.push(format!("  Empate óptimo hacia {}: también via {}", v, u));
+                }
+                continue;
+            }
+            match dist[v] {
+                None => {
+                    dist[v] = Some(alt);
+                    preds[v] = vec![u];
+                    let fv = f(alt, v)?;
+                    heap.push(Entrada { dist: fv, node: v });
+                    log.push(format!(
+                        "  Trazo ({} -> {}, w={}): g[{}]={}, f={}+{}={}",
+                        u,
+                        v,
+                        w,
+                        v,
+                        alt,
+                        alt,
+                        h(v),
+                        fv
+                    ));
+                }
+                Some(curr) if alt < curr => {
+                    dist[v] = Some(alt);
+                    preds[v] = vec![u];
+                    let fv = f(alt, v)?;
+                    heap.push(Entrada { dist: fv, node: v });
+                    log.push(format!(
+                        "  Mejora ({} -> {}, w={}): g[{}] {}→{}, f={}",
+                        u, v, w, v, curr, alt, fv
+                    ));
+                }
+                Some(curr) if alt == curr && !preds[v].contains(&u) => {
+                    preds[v].push(u);
+                    log.push(format!("  Empate óptimo hacia {}: también via {}", v, u));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    match dist[destino] {
+        Some(d) => log.push(format!(
+            "Destino {} fijado con g={}, {} nodos expandidos",
+            destino, d, expandidos
+        )),
+        None => log.push(format!(
+            "Destino {} no alcanzable, {} nodos expandidos",
+            destino, expandidos
+        )),
+    }
+    Ok((dist, preds, log))
+}
+
+/// Si todos los nodos tienen coordenadas. Las heurísticas solo las usan en ese caso: si unos
+/// nodos estiman la distancia y otros 0, h deja de ser consistente y A* puede devolver un camino
+/// que no es el mínimo.
+pub fn coordenadas_completas(coords: &Coordenadas) -> bool {
+    !coords.is_empty() && coords.iter().all(Option::is_some)
+}
+
+/// Heurística de distancia euclídea hasta `destino`, multiplicada por `escala`
+/// (pesos por unidad de distancia). `coords` tiene una entrada por nodo, como las de
+/// [`GrafoManual::generar_con_coordenadas`](crate::grafo::GrafoManual::generar_con_coordenadas);
+/// si alguno no tiene coordenadas (ver [`coordenadas_completas`]) estima 0 en todos.
+pub fn euclidiana<W: Peso>(
+    coords: &Coordenadas,
+    destino: usize,
+    escala: f64,
+) -> impl Fn(usize) -> W + '_ {
+    heuristica(coords, destino, move |dx, dy| dx.hypot(dy) * escala)
+}
+
+/// Heurística de distancia Manhattan (`|dx| + |dy|`) hasta `destino`, multiplicada por
+/// `escala`. Solo es admisible si las aristas siguen una grilla. Sin coordenadas en todos los
+/// nodos estima 0, como [`euclidiana`].
+pub fn manhattan<W: Peso>(
+    coords: &Coordenadas,
+    destino: usize,
+    escala: f64,
+) -> impl Fn(usize) -> W + '_ {
+    heuristica(coords, destino, move |dx, dy| {
+        (dx.abs() + dy.abs()) * escala
+    })
+}
+
+// La distancia de cada nodo al destino según `medir(dx, dy)`, o 0 en todos si falta alguna
+fn heuristica<'a, W: Peso>(
+    coords: &'a Coordenadas,
+    destino: usize,
+    medir: impl Fn(f64, f64) -> f64 + 'a,
+) -> impl Fn(usize) -> W + 'a {
+    let completas = coordenadas_completas(coords);
+    move |v| match (
+        coords.get(v).copied().flatten(),
+        coords.get(destino).copied().flatten(),
+    ) {
+        (Some((x1, y1)), Some((x2, y2))) if completas => W::desde_f64(medir(x1 - x2, y1 - y2)),
+        _ => W::cero(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::{dijkstra_detallado, reconstruir_todos_caminos};
+    use crate::grafo::{GrafoManual, de_texto};
+
+    #[test]
+    fn con_coordenadas_da_lo_mismo_que_dijkstra_y_guarda_empates() {
+        let (g, coords) = GrafoManual::new(
+            vec![],
+            "O A 1\nO B 1\nA T 1\nB T 1\nO T 5\npos: O 0 0\npos: A 1 0\npos: B 0 1\npos: T 1 1"
+                .into(),
+        )
+        .generar_con_coordenadas::<i64>()
+        .unwrap();
+        assert!(coordenadas_completas(&coords));
+        let h = manhattan::<i64>(&coords, 3, 1.0);
+        assert_eq!((h(0), h(1), h(3)), (2, 1, 0));
+        let (dist, preds, _) = astar_detallado(&g, 0, 3, h).unwrap();
+        assert_eq!(dist[3], Some(2));
+        let mut caminos = reconstruir_todos_caminos(&preds, 0, 3);
+        caminos.sort();
+        assert_eq!(caminos, vec![vec![0, 1, 3], vec![0, 2, 3]]);
+    }
+
+    #[test]
+    fn empates_por_aristas_de_peso_cero_como_dijkstra() {
+        // T sale antes que A (con empates sale primero el índice mayor), así el empate por A -> T
+        // llega cuando T ya está cerrado
+        let g = de_texto::<i64>("O A 1\nO B 1\nA T 0\nB T 0");
+        let (dist, preds, _) = astar_detallado(&g, 0, 3, |_| 0).unwrap();
+        let (dist_d, preds_d, _) = dijkstra_detallado(&g, 0).unwrap();
+        assert_eq!(dist[3], dist_d[3]);
+        let mut p = preds[3].clone();
+        p.sort();
+        assert_eq!(p, vec![1, 2]);
+        let mut p_d = preds_d[3].clone();
+        p_d.sort();
+        assert_eq!(p, p_d);
+    }
+
+    #[test]
+    fn destino_inalcanzable() {
+        let g = de_texto::<i64>("A B 1\nC D 1");
+        let (dist, preds, _) = astar_detallado(&g, 0, 3, |_| 0).unwrap();
+        assert_eq!(dist[3], None);
+        assert!(reconstruir_todos_caminos(&preds, 0, 3).is_empty());
+    }
+
+    #[test]
+    fn sin_coordenadas_en_todos_los_nodos_estima_cero() {
+        // con solo A y T ubicados h(A) = 10 y h(B) = 0: A* fijaría T por B (6) antes de mirar A
+        let (g, coords) = GrafoManual::new(
+            vec![],
+            "nodos: O A B T\nO A 1\nA T 1\nO B 1\nB T 5\npos: A 0 0\npos: T 10 0".into(),
+        )
+        .generar_con_coordenadas::<i64>()
+        .unwrap();
+        assert!(!coordenadas_completas(&coords));
+        let h = euclidiana::<i64>(&coords, 3, 1.0);
+        assert!((0..4).all(|v| h(v) == 0));
+        let (dist, _, _) = astar_detallado(&g, 0, 3, h).unwrap();
+        assert_eq!(dist[3], Some(2));
+    }
+
+    #[test]
+    fn coordenadas_cortas_no_entran_en_panico() {
+        let coords: Coordenadas = vec![Some((0.0, 0.0))];
+        assert_eq!(manhattan::<i64>(&coords, 5, 1.0)(0), 0);
+    }
+}
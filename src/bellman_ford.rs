@@ -0,0 +1,280 @@
+use crate::dijkstra::{Entrada, Resultado, es_ancestro};
+use crate::peso::Peso;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use std::collections::BinaryHeap;
+use std::fmt;
+
+// Bellman-Ford para cuando hay pesos negativos (dijkstra no los acepta). Es O(V·E), así que
+// para grafos sin negativos conviene seguir usando dijkstra_detallado.
+
+/// Por qué [`bellman_ford_detallado`] no pudo dar distancias.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FalloBellmanFord {
+    /// Hay un ciclo de peso negativo alcanzable desde el origen. `ciclo` son los nodos en orden,
+    /// cerrando en el primero (`[a, b, c, a]`), y `log` la traza hasta detectarlo.
+    CicloNegativo { ciclo: Vec<usize>, log: Vec<String> },
+    /// Alguna distancia no cabe en el tipo de peso.
+    Desborde(String),
+}
+
+impl fmt::Display for FalloBellmanFord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FalloBellmanFord::CicloNegativo { ciclo, .. } => {
+                write!(f, "Ciclo negativo alcanzable: {}", unir(ciclo))
+            }
+            FalloBellmanFord::Desborde(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+/// Bellman-Ford desde `origen`, acepta pesos negativos.
+///
+/// Devuelve lo mismo que [`dijkstra_detallado`](crate::dijkstra::dijkstra_detallado):
+/// `(dist, preds, log)`, con `preds[v]` todos los predecesores empatados en el óptimo, listo para
+/// [`reconstruir_todos_caminos`](crate::dijkstra::reconstruir_todos_caminos). En un ciclo de
+/// peso cero los nodos empatan como predecesores unos de otros; igual que en dijkstra se deja
+/// fuera el empate que cerraría el ciclo en `preds`. Si hay un ciclo negativo alcanzable desde
+/// `origen` no existen distancias mínimas y se devuelve el ciclo, aunque las sumas se desborden
+/// antes de terminar las rondas.
+pub fn bellman_ford_detallado<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+) -> Result<Resultado<W>, FalloBellmanFord> {
+    let n = g.node_count();
+    let mut dist: Vec<Option<W>> = vec![None; n];
+    // último nodo que mejoró a cada uno, sirve para encontrar el ciclo negativo
+    let mut padre: Vec<Option<usize>> = vec![None; n];
+    let mut log: Vec<String> = vec![];
+
+    dist[origen] = Some(W::cero());
+    log.push(format!("Inicializo dist[{}]=0, resto = ∞", origen));
+
+    // n-1 rondas bastan sin ciclos negativos, la ronda n es solo para detectarlos
+    let mut cambiado = None;
+    for ronda in 1..=n {
+        log.push(format!("Ronda {}", ronda));
+        cambiado = None;
+        for e in g.edge_references() {
+            let u = e.source().index();
+            let v = e.target().index();
+            let w = *e.weight();
+            let Some(d_u) = dist[u] else {
+                continue;
+            };
+            let Some(alt) = d_u.sumar(w) else {
+                // con pesos grandes un ciclo negativo desborda antes de la ronda n
+                if let Some(ciclo) = ciclo_en_padres(&padre, u) {
+                    log.push(format!(
+                        "Desborde en ({} -> {}) por el ciclo negativo {}",
+                        u,
+                        v,
+                        unir(&ciclo)
+                    ));
+                    return Err(FalloBellmanFord::CicloNegativo { ciclo, log });
+                }
+                return Err(FalloBellmanFord::Desborde(format!(
+                    "Desborde: dist[{}]={} + w={} ({} -> {}) no cabe en el tipo de peso",
+                    u, d_u, w, u, v
+                )));
+            };
+            match dist[v] {
+                None => {
+                    log.push(format!(
+                        "  Trazo ({} -> {}, w={}): dist[{}]={}",
+                        u, v, w, v, alt
+                    ));
+                }
+                Some(curr) if alt < curr => {
+                    log.push(format!(
+                        "  Mejora ({} -> {}, w={}): dist[{}] {}→{}",
+                        u, v, w, v, curr, alt
+                    ));
+                }
+                _ => continue,
+            }
+            dist[v] = Some(alt);
+            padre[v] = Some(u);
+            cambiado = Some(v);
+        }
+        if cambiado.is_none() {
+            log.push(format!("  Sin cambios en la ronda {}, termino", ronda));
+            break;
+        }
+    }
+
+    // si en la ronda n todavía se mejoró algo hay un ciclo negativo
+    if let Some(v) = cambiado {
+        let ciclo =
+            ciclo_en_padres(&padre, v).expect("lo mejorado en la ronda n cuelga de un ciclo");
+        log.push(format!(
+            "La ronda {} todavía mejora: ciclo negativo {}",
+            n,
+            unir(&ciclo)
+        ));
+        return Err(FalloBellmanFord::CicloNegativo { ciclo, log });
+    }
+
+    let preds = preds_empatados(g, &dist, origen);
+    log.push(format!(
+        "Fin: {} nodos alcanzados",
+        dist.iter().filter(|d| d.is_some()).count()
+    ));
+    Ok((dist, preds, log))
+}
+
+// Un ciclo entre los padres siempre es negativo. Si subiendo n veces desde `v` no se llega al
+// origen (que no tiene padre) se cayó dentro de uno; se devuelve cerrando en el primer nodo.
+fn ciclo_en_padres(padre: &[Option<usize>], mut v: usize) -> Option<Vec<usize>> {
+    for _ in 0..padre.len() {
+        v = padre[v]?;
+    }
+    let inicio = v;
+    let mut ciclo = vec![inicio];
+    let mut x = padre[inicio]?;
+    while x != inicio {
+        ciclo.push(x);
+        x = padre[x]?;
+    }
+    ciclo.push(inicio);
+    ciclo.reverse();
+    Some(ciclo)
+}
+
+fn unir(ciclo: &[usize]) -> String {
+    ciclo
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+// Con las distancias finales, los predecesores empatados son los u con dist[u] + w == dist[v].
+// Se recorren las aristas "justas" desde el origen con la misma cola que dijkstra y, como ahí,
+// un empate se agrega salvo que `v` ya sea ancestro de `u`: eso cerraría un ciclo (de peso cero,
+// el único posible entre aristas justas) y reconstruir_todos_caminos se quedaría dando vueltas.
+pub(crate) fn preds_empatados<W: Peso>(
+    g: &DiGraph<String, W>,
+    dist: &[Option<W>],
+    origen: usize,
+) -> Vec<Vec<usize>> {
+    let n = g.node_count();
+    let mut preds: Vec<Vec<usize>> = vec![vec![]; n];
+    let mut visitado = vec![false; n];
+    let mut heap: BinaryHeap<Entrada<W>> = BinaryHeap::new();
+    heap.push(Entrada {
+        dist: W::cero(),
+        node: origen,
+    });
+    while let Some(Entrada { node: u, .. }) = heap.pop() {
+        if std::mem::replace(&mut visitado[u], true) {
+            continue;
+        }
+        let Some(d_u) = dist[u] else {
+            continue;
+        };
+        for e in g.edges(NodeIndex::new(u)) {
+            let v = e.target().index();
+            let Some(d_v) = dist[v] else {
+                continue;
+            };
+            if v == u || d_u.sumar(*e.weight()) != Some(d_v) {
+                continue;
+            }
+            if !preds[v].contains(&u) && !es_ancestro(&preds, v, u) {
+                preds[v].push(u);
+            }
+            if !visitado[v] {
+                heap.push(Entrada { dist: d_v, node: v });
+            }
+        }
+    }
+    preds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::{dijkstra_detallado, reconstruir_todos_caminos};
+    use crate::grafo::de_texto;
+
+    #[test]
+    fn acepta_pesos_negativos() {
+        let g = de_texto::<i64>("A B 4\nA C 2\nC B -3\nB D 1");
+        let (dist, preds, _) = bellman_ford_detallado(&g, 0).unwrap();
+        assert_eq!(dist, vec![Some(0), Some(-1), Some(2), Some(0)]);
+        assert_eq!(
+            reconstruir_todos_caminos(&preds, 0, 3),
+            vec![vec![0, 2, 1, 3]]
+        );
+    }
+
+    #[test]
+    fn paralelas_usan_la_mas_barata() {
+        let g = de_texto::<i64>("A B 5\nA B -1");
+        let (dist, preds, _) = bellman_ford_detallado(&g, 0).unwrap();
+        assert_eq!(dist[1], Some(-1));
+        assert_eq!(preds[1], vec![0]);
+    }
+
+    #[test]
+    fn empates_con_ciclo_de_peso_cero() {
+        // B <-> C con peso 0: los dos empatan como predecesor del otro, pero solo uno puede
+        // quedar sin cerrar un ciclo en preds; queda el mismo que en dijkstra
+        let g = de_texto::<i64>("A B 1\nA C 1\nB C 0\nC B 0\nB D 0");
+        let (dist, preds, _) = bellman_ford_detallado(&g, 0).unwrap();
+        assert_eq!(dist, vec![Some(0), Some(1), Some(1), Some(1)]);
+        assert_eq!(preds, vec![vec![], vec![0, 2], vec![0], vec![1]]);
+        assert_eq!(preds, dijkstra_detallado(&g, 0).unwrap().1);
+        let mut caminos = reconstruir_todos_caminos(&preds, 0, 3);
+        caminos.sort();
+        assert_eq!(caminos, vec![vec![0, 1, 3], vec![0, 2, 1, 3]]);
+
+        // un ciclo de peso cero que vuelve al origen no le da predecesores
+        let g = de_texto::<i64>("A B 0\nB C 0\nC A 0");
+        let (_, preds, _) = bellman_ford_detallado(&g, 0).unwrap();
+        assert_eq!(preds, vec![vec![], vec![0], vec![1]]);
+    }
+
+    #[test]
+    fn reporta_el_ciclo_negativo() {
+        let g = de_texto::<i64>("A B 1\nB C -2\nC B 1\nC D 1");
+        let Err(FalloBellmanFord::CicloNegativo { ciclo, .. }) = bellman_ford_detallado(&g, 0)
+        else {
+            panic!("tenía que encontrar el ciclo B -> C -> B");
+        };
+        assert_eq!(ciclo.len(), 3);
+        assert_eq!(ciclo[0], ciclo[2]);
+        assert!(ciclo.contains(&1) && ciclo.contains(&2));
+    }
+
+    #[test]
+    fn ciclo_negativo_que_desborda_no_es_desborde() {
+        // B -> C -> B baja 2^63 por vuelta: desborda en la segunda ronda, antes de la ronda n
+        let g = de_texto::<i64>("A B 1\nB C -4611686018427387904\nC B -4611686018427387904");
+        let Err(FalloBellmanFord::CicloNegativo { ciclo, .. }) = bellman_ford_detallado(&g, 0)
+        else {
+            panic!("tenía que encontrar el ciclo B -> C -> B");
+        };
+        assert_eq!(ciclo.len(), 3);
+        assert!(ciclo.contains(&1) && ciclo.contains(&2));
+    }
+
+    #[test]
+    fn ciclo_negativo_inalcanzable_no_molesta() {
+        let g = de_texto::<i64>("A B 1\nC D -1\nD C -1");
+        let (dist, preds, _) = bellman_ford_detallado(&g, 0).unwrap();
+        assert_eq!(dist, vec![Some(0), Some(1), None, None]);
+        assert!(reconstruir_todos_caminos(&preds, 0, 3).is_empty());
+    }
+
+    #[test]
+    fn avisa_el_desborde() {
+        let g = de_texto::<i32>("A B 2000000000\nB C 2000000000");
+        assert!(matches!(
+            bellman_ford_detallado(&g, 0),
+            Err(FalloBellmanFord::Desborde(_))
+        ));
+    }
+}
@@ -0,0 +1,371 @@
+use crate::dijkstra::{Entrada, Evento, reconstruir_todos_caminos};
+use crate::peso::Peso;
+use petgraph::Direction;
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use std::collections::{BinaryHeap, HashSet};
+use std::fmt;
+
+// Dijkstra bidireccional: un frente avanza desde el origen y otro desde el destino sobre las
+// aristas invertidas, y se para cuando ya no pueden encontrar nada mejor que el mejor camino
+// visto (mu). Para consultas de un par origen/destino suele fijar muchos menos nodos.
+
+/// Un paso de [`dijkstra_bidireccional`]. Los eventos de cada frente son los mismos
+/// [`Evento`]s de Dijkstra; en el frente de atrás las aristas están invertidas, así que
+/// `Descubre { u, v, .. }` corresponde a la arista original `v -> u`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EventoBidireccional<W> {
+    /// Paso del frente que sale del origen.
+    Adelante(Evento<W>),
+    /// Paso del frente que sale del destino.
+    Atras(Evento<W>),
+    /// Los frentes se tocaron en `u -> v` (o en un nodo si `u == v`) y el mejor camino
+    /// conocido bajó a `mu`.
+    Encuentro { u: usize, v: usize, mu: W },
+    /// Los topes de ambas colas suman más que `mu`, ya no hay caminos mejores ni empatados.
+    Parada {
+        tope_adelante: W,
+        tope_atras: W,
+        mu: W,
+    },
+}
+
+impl<W: fmt::Display> fmt::Display for EventoBidireccional<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventoBidireccional::Adelante(e) => write!(f, "[→] {}", e),
+            EventoBidireccional::Atras(e) => write!(f, "[←] {}", e),
+            EventoBidireccional::Encuentro { u, v, mu } if u == v => {
+                write!(f, "Encuentro en {}: mejor camino = {}", u, mu)
+            }
+            EventoBidireccional::Encuentro { u, v, mu } => {
+                write!(f, "Encuentro por ({} -> {}): mejor camino = {}", u, v, mu)
+            }
+            EventoBidireccional::Parada {
+                tope_adelante,
+                tope_atras,
+                mu,
+            } => write!(
+                f,
+                "Paro: {} + {} > {}, no quedan caminos iguales o mejores",
+                tope_adelante, tope_atras, mu
+            ),
+        }
+    }
+}
+
+// Estado de uno de los dos frentes, es el mismo Dijkstra de siempre pero paso a paso
+struct Frente<W> {
+    dir: Direction,
+    dist: Vec<Option<W>>,
+    preds: Vec<Vec<usize>>,
+    visitado: Vec<bool>,
+    heap: BinaryHeap<Entrada<W>>,
+}
+
+impl<W: Peso> Frente<W> {
+    fn new(n: usize, inicio: usize, dir: Direction) -> Self {
+        let mut dist = vec![None; n];
+        dist[inicio] = Some(W::cero());
+        let mut heap = BinaryHeap::new();
+        heap.push(Entrada {
+            dist: W::cero(),
+            node: inicio,
+        });
+        Self {
+            dir,
+            dist,
+            preds: vec![vec![]; n],
+            visitado: vec![false; n],
+            heap,
+        }
+    }
+
+    // tope de la cola sin contar entradas viejas de nodos ya fijados
+    fn tope(&mut self) -> Option<W> {
+        while let Some(e) = self.heap.peek() {
+            if !self.visitado[e.node] {
+                return Some(e.dist);
+            }
+            self.heap.pop();
+        }
+        None
+    }
+
+    // Fija el siguiente nodo y relaja sus aristas (en la dirección del frente). Devuelve el nodo
+    // fijado y los eventos del paso.
+    fn paso(&mut self, g: &DiGraph<String, W>) -> Result<(usize, Vec<Evento<W>>), String> {
+        let Entrada { dist: d_u, node: u } = self.heap.pop().expect("tope() revisó la cola");
+        self.visitado[u] = true;
+        let mut eventos = vec![Evento::Selecciona {
+            nodo: u,
+            dist: d_u,
+            heap: self.heap.len(),
+        }];
+
+        for e in g.edges_directed(petgraph::prelude::NodeIndex::new(u), self.dir) {
+            let v = match self.dir {
+                Direction::Outgoing => e.target().index(),
+                Direction::Incoming => e.source().index(),
+            };
+            let w = *e.weight();
+            if w < W::cero() {
+                return Err(format!(
+                    "Peso negativo en la arista {} -> {} (w={}), usa Bellman-Ford",
+                    e.source().index(),
+                    e.target().index(),
+                    w
+                ));
+            }
+            if self.visitado[v] {
+                continue;
+            }
+            let alt = d_u.sumar(w).ok_or_else(|| {
+                format!(
+                    "Desborde: dist[{}]={} + w={} no cabe en el tipo de peso",
+                    u, d_u, w
+                )
+            })?;
+            match self.dist[v] {
+                None => {
+                    self.dist[v] = Some(alt);
+                    self.preds[v] = vec![u];
+                    self.heap.push(Entrada { dist: alt, node: v });
+                    eventos.push(Evento::Descubre {
+                        u,
+                        v,
+                        w,
+                        dist: alt,
+                        heap: self.heap.len(),
+                    });
+                }
+                Some(curr) if alt < curr => {
+                    self.dist[v] = Some(alt);
+                    self.preds[v] = vec![u];
+                    self.heap.push(Entrada { dist: alt, node: v });
+                    eventos.push(Evento::Mejora {
+                        u,
+                        v,
+                        w,
+                        antes: curr,
+                        despues: alt,
+                        heap: self.heap.len(),
+                    });
+                }
+                Some(curr) if alt == curr && !self.preds[v].contains(&u) => {
+                    self.preds[v].push(u);
+                    eventos.push(Evento::Empate {
+                        u,
+                        v,
+                        dist: alt,
+                        heap: self.heap.len(),
+                    });
+                }
+                _ => {}
+            }
+        }
+        Ok((u, eventos))
+    }
+}
+
+fn sumar_todo<W: Peso>(terminos: &[W]) -> Result<W, String> {
+    terminos
+        .iter()
+        .try_fold(W::cero(), |acc, &x| acc.sumar(x))
+        .ok_or_else(|| {
+            let texto = terminos
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(" + ");
+            format!("Desborde sumando {}", texto)
+        })
+}
+
+/// Lo que devuelve [`dijkstra_bidireccional`]: `(distancia, caminos, eventos)`.
+pub type ResultadoBidireccional<W> = (Option<W>, Vec<Vec<usize>>, Vec<EventoBidireccional<W>>);
+
+/// Dijkstra bidireccional de `origen` a `destino`.
+///
+/// Devuelve `(distancia, caminos, eventos)`: la distancia mínima (`None` si no es alcanzable),
+/// todos los caminos mínimos empatados (como [`reconstruir_todos_caminos`]) y la traza de ambos
+/// frentes. Los pesos deben ser no negativos; devuelve `Err` con negativos o desborde.
+///
+/// Con aristas de peso 0 algunos empates pueden quedar fuera; si hacen falta todos,
+/// [`dijkstra_detallado_hasta`](crate::dijkstra::dijkstra_detallado_hasta) los registra.
+pub fn dijkstra_bidireccional<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+    destino: usize,
+) -> Result<ResultadoBidireccional<W>, String> {
+    let n = g.node_count();
+    let mut ad: Frente<W> = Frente::new(n, origen, Direction::Outgoing);
+    let mut at: Frente<W> = Frente::new(n, destino, Direction::Incoming);
+    let mut eventos = vec![
+        EventoBidireccional::Adelante(Evento::Inicio { origen, heap: 1 }),
+        EventoBidireccional::Atras(Evento::Inicio {
+            origen: destino,
+            heap: 1,
+        }),
+    ];
+    let mut mu: Option<W> = None;
+    let mut parada = false;
+
+    // si una cola se vacía ese frente ya fijó todo lo alcanzable, mu no puede mejorar
+    while let (Some(tf), Some(tb)) = (ad.tope(), at.tope()) {
+        // se para con ">" y no con ">=" para no perder caminos empatados con mu
+        if let Some(m) = mu
+            // si la suma se desborda seguro que supera a mu
+            && tf.sumar(tb).is_none_or(|t| t > m)
+        {
+            eventos.push(EventoBidireccional::Parada {
+                tope_adelante: tf,
+                tope_atras: tb,
+                mu: m,
+            });
+            parada = true;
+            break;
+        }
+
+        // avanza el frente con el tope más chico
+        let adelante = tf <= tb;
+        let (frente, otro) = if adelante {
+            (&mut ad, &at)
+        } else {
+            (&mut at, &ad)
+        };
+        let (u, evs) = frente.paso(g)?;
+        for e in evs {
+            eventos.push(if adelante {
+                EventoBidireccional::Adelante(e)
+            } else {
+                EventoBidireccional::Atras(e)
+            });
+        }
+
+        // ¿se tocan los frentes? primero en el propio u, luego por sus aristas
+        let d_u = frente.dist[u].expect("u recién fijado");
+        let mut candidatos = vec![];
+        if let Some(d_o) = otro.dist[u] {
+            candidatos.push((u, u, sumar_todo(&[d_u, d_o])?));
+        }
+        for e in g.edges_directed(petgraph::prelude::NodeIndex::new(u), frente.dir) {
+            let v = match frente.dir {
+                Direction::Outgoing => e.target().index(),
+                Direction::Incoming => e.source().index(),
+            };
+            if let Some(d_o) = otro.dist[v] {
+                let (a, b) = if adelante { (u, v) } else { (v, u) };
+                candidatos.push((a, b, sumar_todo(&[d_u, *e.weight(), d_o])?));
+            }
+        }
+        for (a, b, c) in candidatos {
+            if mu.is_none_or(|m| c < m) {
+                mu = Some(c);
+                eventos.push(EventoBidireccional::Encuentro { u: a, v: b, mu: c });
+            }
+        }
+    }
+
+    let Some(mu) = mu else {
+        return Ok((None, vec![], eventos));
+    };
+
+    // Si un frente vació su cola fijó todo lo alcanzable y sus preds ya tienen todos los caminos
+    if !parada {
+        let caminos = if ad.tope().is_none() {
+            reconstruir_todos_caminos(&ad.preds, origen, destino)
+        } else {
+            reconstruir_todos_caminos(&at.preds, destino, origen)
+                .into_iter()
+                .map(|mut c| {
+                    c.reverse();
+                    c
+                })
+                .collect()
+        };
+        return Ok((Some(mu), caminos, eventos));
+    }
+
+    // Si paró por el criterio, cada camino mínimo tiene una arista u -> v con u fijado adelante
+    // y v fijado atrás (o un nodo fijado por los dos), así que se arma prefijo + sufijo desde
+    // todos esos puntos y se quitan los repetidos.
+    // (los puntos de partida tienen distancia 0 exacta aunque su frente no los haya sacado)
+    ad.visitado[origen] = true;
+    at.visitado[destino] = true;
+    let mut caminos = vec![];
+    let mut vistos: HashSet<Vec<usize>> = HashSet::new();
+    let mut juntar = |u: usize, v: usize, caminos: &mut Vec<Vec<usize>>| {
+        for pre in reconstruir_todos_caminos(&ad.preds, origen, u) {
+            for mut suf in reconstruir_todos_caminos(&at.preds, destino, v) {
+                suf.reverse();
+                let mut c = pre.clone();
+                c.extend(if u == v { &suf[1..] } else { &suf[..] });
+                // con ciclos de peso cero prefijo y sufijo pueden repetir nodos
+                let simple = c.iter().collect::<HashSet<_>>().len() == c.len();
+                if simple && vistos.insert(c.clone()) {
+                    caminos.push(c);
+                }
+            }
+        }
+    };
+    for u in (0..n).filter(|&u| ad.visitado[u]) {
+        let d_u = ad.dist[u].expect("fijado");
+        if at.visitado[u] && sumar_todo(&[d_u, at.dist[u].expect("fijado")])? == mu {
+            juntar(u, u, &mut caminos);
+        }
+        for e in g.edges(petgraph::prelude::NodeIndex::new(u)) {
+            let v = e.target().index();
+            if v != u
+                && at.visitado[v]
+                && sumar_todo(&[d_u, *e.weight(), at.dist[v].expect("fijado")])? == mu
+            {
+                juntar(u, v, &mut caminos);
+            }
+        }
+    }
+
+    Ok((Some(mu), caminos, eventos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grafo::de_texto;
+
+    #[test]
+    fn para_por_el_criterio_y_junta_los_empates() {
+        // C y D lejos dejan las dos colas con algo cuando los frentes ya se cruzaron
+        let g = de_texto::<i64>("O A 1\nO B 1\nA T 1\nB T 1\nO C 10\nD T 10");
+        let (dist, mut caminos, eventos) = dijkstra_bidireccional(&g, 0, 3).unwrap();
+        assert_eq!(dist, Some(2));
+        caminos.sort();
+        assert_eq!(caminos, vec![vec![0, 1, 3], vec![0, 2, 3]]);
+        assert!(matches!(
+            eventos.last(),
+            Some(EventoBidireccional::Parada { mu: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn paralelas_dan_un_solo_camino() {
+        let g = de_texto::<i64>("A B 5\nA B 2\nB C 1");
+        let (dist, caminos, _) = dijkstra_bidireccional(&g, 0, 2).unwrap();
+        assert_eq!(dist, Some(3));
+        assert_eq!(caminos, vec![vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn destino_inalcanzable() {
+        let g = de_texto::<i64>("A B 1\nC D 1");
+        let (dist, caminos, _) = dijkstra_bidireccional(&g, 0, 3).unwrap();
+        assert_eq!(dist, None);
+        assert!(caminos.is_empty());
+    }
+
+    #[test]
+    fn rechaza_pesos_negativos() {
+        let g = de_texto::<i64>("A B 1\nB C -1");
+        assert!(dijkstra_bidireccional(&g, 0, 2).is_err());
+    }
+}
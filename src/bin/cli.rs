@@ -0,0 +1,1101 @@
+// Versión de consola del visualizador, pensada para scripts y CI: misma entrada que el modo
+// manual de la interfaz (`U V peso`, una arista por línea, nodos con cualquier nombre) y
+// mismos resultados, sin ventanas.
+//
+// Códigos de salida: 0 ok, 1 error leyendo/parseando el grafo, 2 argumentos inválidos,
+// 3 destino no alcanzable, 4 ciclo negativo (solo bellman-ford).
+use petgraph::graph::{DiGraph, EdgeIndex};
+use projecto_computacional::astar::{coordenadas_completas, euclidiana, manhattan};
+use projecto_computacional::dijkstra::formatear_log;
+use projecto_computacional::{
+    Coordenadas, FalloBellmanFord, Giros, GrafoLeido, GrafoManual, Horarios, OpcionesConsulta,
+    Peso, Real, arborescencia_minima, astar_detallado, bellman_ford_detallado,
+    camino_con_presupuesto, camino_mas_ancho, camino_mas_confiable, caminos_disjuntos,
+    contar_caminos, dijkstra_bidireccional, dijkstra_con_giros, dijkstra_con_horario,
+    dijkstra_detallado_con, flujo_costo_minimo, flujo_maximo, frente_pareto, gen_labels,
+    k_caminos_mas_cortos, kruskal, prim, reconstruir_caminos_limitado, ruta_con_paradas,
+};
+use std::io::Read;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+const USO: &str = "Uso: projecto_cli [ARCHIVO|-] --from U [--to V] [opciones]
+
+  ARCHIVO     lista de aristas `U V peso`, una por línea (por defecto o con `-` se lee stdin)
+  --from U    etiqueta del nodo origen
+  --to V      etiqueta del nodo destino (no hace falta con prim, kruskal ni arborescencia)
+  --nodos N   declara de antemano N nodos A, B, ... (por defecto solo los del archivo)
+  --pesos T   tipo de los pesos: entero (i64, por defecto), real (decimales) o grande (i128)
+  --algoritmo A  dijkstra (por defecto), bellman-ford (acepta pesos negativos), astar,
+                 bidireccional, yen (los k caminos simples más cortos), pareto (el
+                 frente de Pareto entre el peso y el recurso de la cuarta columna),
+                 ancho (maximiza la arista más chica del camino), confiable (los pesos
+                 son probabilidades entre 0 y 1, maximiza su producto), horario (llegada
+                 más temprana con los horarios `salida=duración` de cada arista), giros
+                 (con los costos de `costo: U c`, `giro: U V W c` y `prohibido: U V W`),
+                 disjuntos (el par de caminos sin aristas en común más barato), flujo
+                 (flujo máximo con las capacidades `cap=N`, 1 si falta), flujo-costo
+                 (flujo máximo de costo mínimo, el peso es el costo por unidad), prim o
+                 kruskal (árbol generador mínimo leyendo las aristas sin dirección) o
+                 arborescencia (la arborescencia mínima desde --from)
+  --heuristica H  para astar: euclidiana (por defecto), manhattan o cero, con las
+                  coordenadas de las líneas `pos: U x y` (si falta en algún nodo, h = 0)
+  --escala X  para astar: peso por unidad de distancia de la heurística, por defecto 1
+  --salida T  para horario: hora de salida del origen, por defecto 0
+  --k N       para yen: cuántos caminos, por defecto 3
+  --sin-nodos-comunes  para disjuntos: los dos caminos tampoco comparten nodos
+  --hasta-destino  para dijkstra: termina apenas fija el destino
+  --por U     para dijkstra: la ruta pasa por U (se puede repetir, en ese orden)
+  --cualquier-orden  con --por: visita las paradas en el orden más barato
+  --presupuesto R  para dijkstra: el camino más barato cuyo recurso (cuarta columna
+                   `U V peso recurso`, cero si falta) no pasa de R
+  --evitar U  para dijkstra: no pasa por el nodo U (se puede repetir)
+  --evitar-arista U V  para dijkstra: no usa las aristas U -> V (se puede repetir)
+  --max-caminos N  imprime como mucho N caminos empatados (por defecto todos); no se
+                   aplica con yen, pareto, giros, disjuntos, flujo, los árboles, --por
+                   ni --presupuesto, que no dan caminos empatados
+  --pasos     imprime también el paso a paso del algoritmo (no hay con yen, pareto, --por
+              ni --presupuesto)
+  -h, --help  muestra esta ayuda";
+
+#[derive(Clone, Copy)]
+enum Algoritmo {
+    Dijkstra,
+    BellmanFord,
+    AEstrella,
+    Bidireccional,
+    Yen,
+    Pareto,
+    Ancho,
+    Confiable,
+    Horario,
+    Giros,
+    Disjuntos,
+    Flujo,
+    FlujoCosto,
+    Prim,
+    Kruskal,
+    Arborescencia,
+}
+
+// Cómo se llama el valor de cada nodo en la salida, según el algoritmo
+struct Nombres {
+    tabla: &'static str,
+    caminos: &'static str,
+    valor: &'static str,
+    sin_camino: &'static str,
+}
+
+const DISTANCIA: Nombres = Nombres {
+    tabla: "Distancias",
+    caminos: "Caminos mínimos",
+    valor: "suma de pesos",
+    sin_camino: "∞",
+};
+const CAPACIDAD: Nombres = Nombres {
+    tabla: "Capacidades",
+    caminos: "Caminos más anchos",
+    valor: "capacidad",
+    sin_camino: "sin camino",
+};
+const PROBABILIDAD: Nombres = Nombres {
+    tabla: "Probabilidades",
+    caminos: "Caminos más confiables",
+    valor: "probabilidad",
+    sin_camino: "sin camino",
+};
+const LLEGADA: Nombres = Nombres {
+    tabla: "Llegadas",
+    caminos: "Caminos más rápidos",
+    valor: "llegada",
+    sin_camino: "∞",
+};
+const GIROS: Nombres = Nombres {
+    tabla: "Costos con nodos y giros",
+    caminos: "Camino mínimo",
+    valor: "costo con nodos y giros",
+    sin_camino: "∞",
+};
+
+#[derive(Clone, Copy)]
+enum Heuristica {
+    Euclidiana,
+    Manhattan,
+    Cero,
+}
+
+#[derive(Clone, Copy)]
+enum TipoPeso {
+    Entero,
+    Real,
+    Grande,
+}
+
+struct Args {
+    archivo: Option<String>,
+    from: String,
+    to: Option<String>,
+    nodos: Option<usize>,
+    pesos: TipoPeso,
+    algoritmo: Algoritmo,
+    heuristica: Heuristica,
+    escala: f64,
+    salida: f64,
+    k: usize,
+    sin_nodos_comunes: bool,
+    hasta_destino: bool,
+    por: Vec<String>,
+    cualquier_orden: bool,
+    presupuesto: Option<String>,
+    evitar: Vec<String>,
+    evitar_aristas: Vec<(String, String)>,
+    max_caminos: usize,
+    pasos: bool,
+}
+
+// `Ok(None)` si se pidió la ayuda
+fn parse_args(mut it: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut archivo = None;
+    let mut from = None;
+    let mut to = None;
+    let mut nodos = None;
+    let mut pesos = TipoPeso::Entero;
+    let mut algoritmo = Algoritmo::Dijkstra;
+    let mut heuristica = None;
+    let mut escala = None;
+    let mut salida = None;
+    let mut k = None;
+    let mut sin_nodos_comunes = false;
+    let mut hasta_destino = false;
+    let mut por = vec![];
+    let mut cualquier_orden = false;
+    let mut presupuesto = None;
+    let mut evitar = vec![];
+    let mut evitar_aristas = vec![];
+    let mut max_caminos = None;
+    let mut pasos = false;
+
+    while let Some(a) = it.next() {
+        match a.as_str() {
+            "--from" => from = Some(it.next().ok_or("falta el valor de --from")?),
+            "--to" => to = Some(it.next().ok_or("falta el valor de --to")?),
+            "--nodos" => {
+                let v = it.next().ok_or("falta el valor de --nodos")?;
+                nodos = Some(
+                    v.parse()
+                        .map_err(|_| format!("--nodos inválido: '{}'", v))?,
+                );
+            }
+            "--pesos" => {
+                pesos = match it.next().ok_or("falta el valor de --pesos")?.as_str() {
+                    "entero" => TipoPeso::Entero,
+                    "real" => TipoPeso::Real,
+                    "grande" => TipoPeso::Grande,
+                    t => return Err(format!("--pesos inválido: '{}'", t)),
+                }
+            }
+            "--algoritmo" => {
+                algoritmo = match it.next().ok_or("falta el valor de --algoritmo")?.as_str() {
+                    "dijkstra" => Algoritmo::Dijkstra,
+                    "bellman-ford" => Algoritmo::BellmanFord,
+                    "astar" => Algoritmo::AEstrella,
+                    "bidireccional" => Algoritmo::Bidireccional,
+                    "yen" => Algoritmo::Yen,
+                    "pareto" => Algoritmo::Pareto,
+                    "ancho" => Algoritmo::Ancho,
+                    "confiable" => Algoritmo::Confiable,
+                    "horario" => Algoritmo::Horario,
+                    "giros" => Algoritmo::Giros,
+                    "disjuntos" => Algoritmo::Disjuntos,
+                    "flujo" => Algoritmo::Flujo,
+                    "flujo-costo" => Algoritmo::FlujoCosto,
+                    "prim" => Algoritmo::Prim,
+                    "kruskal" => Algoritmo::Kruskal,
+                    "arborescencia" => Algoritmo::Arborescencia,
+                    a => return Err(format!("--algoritmo inválido: '{}'", a)),
+                }
+            }
+            "--heuristica" => {
+                heuristica = Some(
+                    match it.next().ok_or("falta el valor de --heuristica")?.as_str() {
+                        "euclidiana" => Heuristica::Euclidiana,
+                        "manhattan" => Heuristica::Manhattan,
+                        "cero" => Heuristica::Cero,
+                        h => return Err(format!("--heuristica inválida: '{}'", h)),
+                    },
+                )
+            }
+            "--escala" => {
+                let v = it.next().ok_or("falta el valor de --escala")?;
+                escala = Some(
+                    v.parse()
+                        .map_err(|_| format!("--escala inválida: '{}'", v))?,
+                );
+            }
+            "--salida" => {
+                let v = it.next().ok_or("falta el valor de --salida")?;
+                salida = Some(
+                    v.parse()
+                        .ok()
+                        .filter(|t: &f64| t.is_finite())
+                        .ok_or_else(|| format!("--salida inválida: '{}'", v))?,
+                );
+            }
+            "--k" => {
+                let v = it.next().ok_or("falta el valor de --k")?;
+                k = Some(v.parse().map_err(|_| format!("--k inválido: '{}'", v))?);
+            }
+            "--sin-nodos-comunes" => sin_nodos_comunes = true,
+            "--hasta-destino" => hasta_destino = true,
+            "--por" => por.push(it.next().ok_or("falta el valor de --por")?),
+            "--cualquier-orden" => cualquier_orden = true,
+            "--presupuesto" => {
+                presupuesto = Some(it.next().ok_or("falta el valor de --presupuesto")?)
+            }
+            "--evitar" => evitar.push(it.next().ok_or("falta el valor de --evitar")?),
+            "--evitar-arista" => {
+                let u = it.next().ok_or("faltan los nodos de --evitar-arista")?;
+                let v = it
+                    .next()
+                    .ok_or("falta el segundo nodo de --evitar-arista")?;
+                evitar_aristas.push((u, v));
+            }
+            "--max-caminos" => {
+                let v = it.next().ok_or("falta el valor de --max-caminos")?;
+                max_caminos = Some(
+                    v.parse()
+                        .map_err(|_| format!("--max-caminos inválido: '{}'", v))?,
+                );
+            }
+            "--pasos" => pasos = true,
+            "-h" | "--help" => return Ok(None),
+            "-" => archivo = None,
+            s if s.starts_with("--") => return Err(format!("opción desconocida: {}", s)),
+            _ => {
+                if archivo.is_some() {
+                    return Err(format!("sobra el argumento '{}'", a));
+                }
+                archivo = Some(a);
+            }
+        }
+    }
+
+    if (heuristica.is_some() || escala.is_some()) && !matches!(algoritmo, Algoritmo::AEstrella) {
+        return Err("--heuristica y --escala solo se aplican con astar".into());
+    }
+    if salida.is_some() && !matches!(algoritmo, Algoritmo::Horario) {
+        return Err("--salida solo se aplica con horario".into());
+    }
+    if k.is_some() && !matches!(algoritmo, Algoritmo::Yen) {
+        return Err("--k solo se aplica con yen".into());
+    }
+    if sin_nodos_comunes && !matches!(algoritmo, Algoritmo::Disjuntos) {
+        return Err("--sin-nodos-comunes solo se aplica con disjuntos".into());
+    }
+    let hay_bloqueos = !evitar.is_empty() || !evitar_aristas.is_empty();
+    if hay_bloqueos && !matches!(algoritmo, Algoritmo::Dijkstra) {
+        return Err("--evitar y --evitar-arista solo se aplican con dijkstra".into());
+    }
+    if !por.is_empty() && !matches!(algoritmo, Algoritmo::Dijkstra) {
+        return Err("--por solo se aplica con dijkstra".into());
+    }
+    // con --por y --presupuesto la ruta sale de otro algoritmo, que ya para en el destino
+    if hasta_destino
+        && (!matches!(algoritmo, Algoritmo::Dijkstra) || !por.is_empty() || presupuesto.is_some())
+    {
+        return Err(
+            "--hasta-destino solo se aplica con dijkstra, sin --por ni --presupuesto".into(),
+        );
+    }
+    if cualquier_orden && por.is_empty() {
+        return Err("--cualquier-orden solo se aplica con --por".into());
+    }
+    if !por.is_empty() && hay_bloqueos {
+        return Err("--por no se puede combinar con --evitar".into());
+    }
+    if presupuesto.is_some() && !matches!(algoritmo, Algoritmo::Dijkstra) {
+        return Err("--presupuesto solo se aplica con dijkstra".into());
+    }
+    if presupuesto.is_some() && (hay_bloqueos || !por.is_empty()) {
+        return Err("--presupuesto no se puede combinar con --por ni --evitar".into());
+    }
+
+    // yen, pareto y las rutas con --por o --presupuesto no llevan traza
+    let con_traza = !matches!(algoritmo, Algoritmo::Yen | Algoritmo::Pareto)
+        && por.is_empty()
+        && presupuesto.is_none();
+    if pasos && !con_traza {
+        return Err("--pasos no se aplica con yen, pareto, --por ni --presupuesto".into());
+    }
+    // los demás dan un solo camino, caminos de distinto costo o ninguno
+    let con_empates = matches!(
+        algoritmo,
+        Algoritmo::Dijkstra
+            | Algoritmo::BellmanFord
+            | Algoritmo::AEstrella
+            | Algoritmo::Bidireccional
+            | Algoritmo::Ancho
+            | Algoritmo::Confiable
+            | Algoritmo::Horario
+    ) && por.is_empty()
+        && presupuesto.is_none();
+    if max_caminos.is_some() && !con_empates {
+        return Err(
+            "--max-caminos solo se aplica con dijkstra, bellman-ford, astar, bidireccional, \
+             ancho, confiable y horario, sin --por ni --presupuesto"
+                .into(),
+        );
+    }
+
+    // los árboles generadores no tienen destino
+    let sin_destino = matches!(
+        algoritmo,
+        Algoritmo::Prim | Algoritmo::Kruskal | Algoritmo::Arborescencia
+    );
+    if to.is_none() && !sin_destino {
+        return Err("falta --to".into());
+    }
+
+    Ok(Some(Args {
+        archivo,
+        from: from.ok_or("falta --from")?,
+        to,
+        nodos,
+        pesos,
+        algoritmo,
+        heuristica: heuristica.unwrap_or(Heuristica::Euclidiana),
+        escala: escala.unwrap_or(1.0),
+        salida: salida.unwrap_or(0.0),
+        k: k.unwrap_or(3),
+        sin_nodos_comunes,
+        hasta_destino,
+        por,
+        cualquier_orden,
+        presupuesto,
+        evitar,
+        evitar_aristas,
+        max_caminos: max_caminos.unwrap_or(usize::MAX),
+        pasos,
+    }))
+}
+
+fn leer_entrada(archivo: &Option<String>) -> Result<String, String> {
+    match archivo {
+        Some(ruta) => {
+            std::fs::read_to_string(ruta).map_err(|e| format!("no se pudo leer '{}': {}", ruta, e))
+        }
+        None => {
+            let mut s = String::new();
+            std::io::stdin()
+                .read_to_string(&mut s)
+                .map_err(|e| format!("no se pudo leer stdin: {}", e))?;
+            Ok(s)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(a)) => a,
+        Ok(None) => {
+            println!("{}", USO);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {}", e);
+            eprintln!("{}", USO);
+            return ExitCode::from(2);
+        }
+    };
+
+    let raw = match leer_entrada(&args.archivo) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    match (args.algoritmo, args.pesos) {
+        // las probabilidades son decimales, sin importar --pesos
+        (Algoritmo::Confiable, _) | (_, TipoPeso::Real) => resolver::<Real>(&args, raw),
+        (_, TipoPeso::Entero) => resolver::<i64>(&args, raw),
+        (_, TipoPeso::Grande) => resolver::<i128>(&args, raw),
+    }
+}
+
+// Lo que comparten todos los algoritmos: el grafo leído, los nodos de la consulta y los argumentos
+struct Consulta<'a, W> {
+    args: &'a Args,
+    g: DiGraph<String, W>,
+    labels: Vec<String>,
+    origen: usize,
+    // `None` solo con los árboles generadores, que no lo usan (ver parse_args)
+    destino: Option<usize>,
+}
+
+impl<W> Consulta<'_, W> {
+    fn destino(&self) -> usize {
+        self.destino
+            .expect("parse_args exige --to salvo en los árboles")
+    }
+
+    fn no_alcanzable(&self) -> ExitCode {
+        println!(
+            "Destino {} no alcanzable desde {}",
+            nodo(&self.labels, self.destino()),
+            nodo(&self.labels, self.origen)
+        );
+        ExitCode::from(3)
+    }
+}
+
+fn buscar(labels: &[String], l: &str) -> Option<usize> {
+    labels.iter().position(|s| s == l)
+}
+
+// Todo lo que depende del tipo de peso: construir, correr el algoritmo elegido e imprimir
+fn resolver<W: Peso + FromStr>(args: &Args, raw: String) -> ExitCode {
+    let declarados = args.nodos.map(gen_labels).unwrap_or_default();
+    let GrafoLeido {
+        grafo: g,
+        coords,
+        recursos,
+        horarios,
+        capacidades,
+        giros,
+    } = match GrafoManual::new(declarados, raw).generar_todo::<W, W>() {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+
+    let labels: Vec<String> = g.node_weights().cloned().collect();
+    let Some(origen) = buscar(&labels, &args.from) else {
+        eprintln!(
+            "error: el nodo origen '{}' no existe en el grafo",
+            args.from
+        );
+        return ExitCode::from(2);
+    };
+    let destino = match &args.to {
+        Some(to) => match buscar(&labels, to) {
+            Some(v) => Some(v),
+            None => {
+                eprintln!("error: el nodo destino '{}' no existe en el grafo", to);
+                return ExitCode::from(2);
+            }
+        },
+        None => None,
+    };
+    let c = Consulta {
+        args,
+        g,
+        labels,
+        origen,
+        destino,
+    };
+
+    let resultado = match args.algoritmo {
+        Algoritmo::Dijkstra if args.presupuesto.is_some() => correr_presupuesto(&c, &recursos),
+        Algoritmo::Dijkstra if !args.por.is_empty() => correr_paradas(&c),
+        Algoritmo::Dijkstra => correr_dijkstra(&c),
+        Algoritmo::BellmanFord => correr_bellman_ford(&c),
+        Algoritmo::AEstrella => correr_astar(&c, &coords),
+        Algoritmo::Bidireccional => correr_bidireccional(&c),
+        Algoritmo::Yen => correr_yen(&c),
+        Algoritmo::Pareto => correr_pareto(&c, &recursos),
+        Algoritmo::Ancho => correr_ancho(&c),
+        Algoritmo::Confiable => correr_confiable(&c),
+        Algoritmo::Horario => correr_horario(&c, &horarios),
+        Algoritmo::Giros => correr_giros(&c, &giros),
+        Algoritmo::Disjuntos => correr_disjuntos(&c),
+        Algoritmo::Flujo | Algoritmo::FlujoCosto => correr_flujo(&c, &capacidades),
+        Algoritmo::Prim | Algoritmo::Kruskal | Algoritmo::Arborescencia => correr_arbol(&c),
+    };
+    // los errores del algoritmo (pesos negativos, desbordes...) salen con 1, los de argumentos
+    // ya los imprimió cada uno
+    resultado.unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        ExitCode::from(1)
+    })
+}
+
+fn correr_dijkstra<W: Peso>(c: &Consulta<W>) -> Result<ExitCode, String> {
+    let args = c.args;
+    let mut opciones = OpcionesConsulta {
+        hasta: args.hasta_destino.then_some(c.destino()),
+        ..OpcionesConsulta::default()
+    };
+    for l in &args.evitar {
+        let Some(v) = buscar(&c.labels, l) else {
+            eprintln!("error: el nodo '{}' de --evitar no existe en el grafo", l);
+            return Ok(ExitCode::from(2));
+        };
+        opciones.nodos_bloqueados.insert(v);
+    }
+    for (a, b) in &args.evitar_aristas {
+        let (Some(u), Some(v)) = (buscar(&c.labels, a), buscar(&c.labels, b)) else {
+            eprintln!(
+                "error: la arista '{} {}' de --evitar-arista no existe en el grafo",
+                a, b
+            );
+            return Ok(ExitCode::from(2));
+        };
+        opciones.aristas_bloqueadas.insert((u, v));
+    }
+    let (dist, preds, log) = dijkstra_detallado_con(&c.g, c.origen, &opciones)?;
+    imprimir_pasos(args, &log);
+    // con --hasta-destino para al fijar el destino, las demás distancias pueden no ser las mínimas
+    let nota = if args.hasta_destino {
+        " (solo exactas en los caminos óptimos)"
+    } else {
+        ""
+    };
+    Ok(imprimir_resultado(c, &dist, &preds, nota, &DISTANCIA))
+}
+
+fn correr_bellman_ford<W: Peso>(c: &Consulta<W>) -> Result<ExitCode, String> {
+    let (dist, preds, log) = match bellman_ford_detallado(&c.g, c.origen) {
+        Ok(r) => r,
+        Err(FalloBellmanFord::CicloNegativo { ciclo, log }) => {
+            imprimir_pasos(c.args, &log);
+            println!(
+                "Ciclo negativo alcanzable: {}",
+                texto_camino(&c.labels, &ciclo)
+            );
+            return Ok(ExitCode::from(4));
+        }
+        Err(e) => return Err(e.to_string()),
+    };
+    imprimir_pasos(c.args, &log);
+    Ok(imprimir_resultado(c, &dist, &preds, "", &DISTANCIA))
+}
+
+fn correr_astar<W: Peso>(c: &Consulta<W>, coords: &Coordenadas) -> Result<ExitCode, String> {
+    let (g, origen, destino, escala) = (&c.g, c.origen, c.destino(), c.args.escala);
+    let (dist, preds, log) = match c.args.heuristica {
+        Heuristica::Euclidiana | Heuristica::Manhattan if !coordenadas_completas(coords) => {
+            eprintln!("aviso: no todos los nodos tienen `pos:`, se usa h = 0");
+            astar_detallado(g, origen, destino, |_| W::cero())
+        }
+        Heuristica::Euclidiana => {
+            astar_detallado(g, origen, destino, euclidiana(coords, destino, escala))
+        }
+        Heuristica::Manhattan => {
+            astar_detallado(g, origen, destino, manhattan(coords, destino, escala))
+        }
+        Heuristica::Cero => astar_detallado(g, origen, destino, |_| W::cero()),
+    }?;
+    imprimir_pasos(c.args, &log);
+    // A* para al fijar el destino, las demás distancias pueden no ser las mínimas
+    let nota = " (A*: solo exactas en los caminos óptimos)";
+    Ok(imprimir_resultado(c, &dist, &preds, nota, &DISTANCIA))
+}
+
+// El bidireccional solo calcula el par origen/destino, no hay tabla de distancias
+fn correr_bidireccional<W: Peso>(c: &Consulta<W>) -> Result<ExitCode, String> {
+    let (total, caminos, eventos) = dijkstra_bidireccional(&c.g, c.origen, c.destino())?;
+    imprimir_pasos(c.args, &formatear_log(&eventos));
+    let Some(total) = total else {
+        return Ok(c.no_alcanzable());
+    };
+    let cuantos = Some(caminos.len() as u128);
+    let caminos = &caminos[..caminos.len().min(c.args.max_caminos)];
+    imprimir_caminos(c, caminos, cuantos, &DISTANCIA, total);
+    Ok(ExitCode::SUCCESS)
+}
+
+// Con presupuesto hay un solo camino, con su costo y el recurso que gasta
+fn correr_presupuesto<W: Peso + FromStr>(
+    c: &Consulta<W>,
+    recursos: &[W],
+) -> Result<ExitCode, String> {
+    let p = c.args.presupuesto.as_deref().unwrap_or_default();
+    let Ok(presupuesto) = p.parse::<W>() else {
+        eprintln!("error: --presupuesto inválido: '{}'", p);
+        return Ok(ExitCode::from(2));
+    };
+    let (o, d) = (&c.labels[c.origen], &c.labels[c.destino()]);
+    let Some((camino, costo, recurso)) =
+        camino_con_presupuesto(&c.g, recursos, c.origen, c.destino(), presupuesto)?
+    else {
+        println!(
+            "Ningún camino {} -> {} gasta {} o menos de recurso",
+            o, d, presupuesto
+        );
+        return Ok(ExitCode::from(3));
+    };
+    println!(
+        "Camino {} -> {} con presupuesto {} (suma de pesos = {}, recurso = {}):",
+        o, d, presupuesto, costo, recurso
+    );
+    println!("  {}", texto_camino(&c.labels, &camino));
+    Ok(ExitCode::SUCCESS)
+}
+
+// Con paradas se imprime la ruta y su desglose por tramo en vez de la tabla de distancias
+fn correr_paradas<W: Peso>(c: &Consulta<W>) -> Result<ExitCode, String> {
+    let mut paradas = vec![];
+    for l in &c.args.por {
+        let Some(v) = buscar(&c.labels, l) else {
+            eprintln!("error: la parada '{}' no existe en el grafo", l);
+            return Ok(ExitCode::from(2));
+        };
+        paradas.push(v);
+    }
+    let en_orden = !c.args.cualquier_orden;
+    let Some(ruta) = ruta_con_paradas(&c.g, c.origen, c.destino(), &paradas, en_orden)? else {
+        return Ok(c.no_alcanzable());
+    };
+    println!(
+        "Ruta {} -> {} con paradas (suma de pesos = {}):",
+        c.labels[c.origen],
+        c.labels[c.destino()],
+        ruta.total
+    );
+    println!("  {}", texto_camino(&c.labels, &ruta.camino));
+    for (i, t) in ruta.tramos.iter().enumerate() {
+        println!(
+            "  tramo {}: {}   (costo = {})",
+            i + 1,
+            texto_camino(&c.labels, &t.camino),
+            t.costo
+        );
+    }
+    if !ruta.exacta {
+        println!("  (orden de paradas heurístico, puede no ser el óptimo)");
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+// Los otros semianillos: misma salida que Dijkstra pero con capacidades o probabilidades
+fn correr_ancho<W: Peso>(c: &Consulta<W>) -> Result<ExitCode, String> {
+    let (cap, preds, log) = camino_mas_ancho(&c.g, c.origen);
+    imprimir_pasos(c.args, &log);
+    Ok(imprimir_resultado(c, &cap, &preds, "", &CAPACIDAD))
+}
+
+fn correr_confiable<W: Peso>(c: &Consulta<W>) -> Result<ExitCode, String> {
+    // `g` ya se leyó como Real (ver main), pero aquí el tipo es genérico
+    let g = c.g.map(|_, l| l.clone(), |_, w| Real(w.a_f64()));
+    let (prob, preds, log) = camino_mas_confiable(&g, c.origen)?;
+    imprimir_pasos(c.args, &log);
+    Ok(imprimir_resultado(c, &prob, &preds, "", &PROBABILIDAD))
+}
+
+fn correr_horario<W: Peso>(c: &Consulta<W>, horarios: &Horarios) -> Result<ExitCode, String> {
+    let (llegada, preds, log) = dijkstra_con_horario(&c.g, horarios, c.origen, c.args.salida)?;
+    imprimir_pasos(c.args, &log);
+    let nota = format!(" saliendo en {}", c.args.salida);
+    Ok(imprimir_resultado(c, &llegada, &preds, &nota, &LLEGADA))
+}
+
+// Con giros hay un solo camino por destino y puede repetir nodos, no hay preds
+fn correr_giros<W: Peso>(c: &Consulta<W>, giros: &Giros<W>) -> Result<ExitCode, String> {
+    let r = dijkstra_con_giros(&c.g, giros, c.origen)?;
+    imprimir_pasos(c.args, &r.log);
+    imprimir_tabla(c, &r.dist, "", &GIROS);
+    let (Some(total), Some(camino)) = (r.dist[c.destino()], r.camino(c.destino())) else {
+        return Ok(c.no_alcanzable());
+    };
+    imprimir_caminos(c, &[camino], Some(1), &GIROS, total);
+    Ok(ExitCode::SUCCESS)
+}
+
+// Yen da caminos de distinto costo, cada uno con su suma
+fn correr_yen<W: Peso>(c: &Consulta<W>) -> Result<ExitCode, String> {
+    let caminos = k_caminos_mas_cortos(&c.g, c.origen, c.destino(), c.args.k)?;
+    if caminos.is_empty() {
+        return Ok(c.no_alcanzable());
+    }
+    println!(
+        "Los {} caminos más cortos {} -> {}:",
+        caminos.len(),
+        c.labels[c.origen],
+        c.labels[c.destino()]
+    );
+    imprimir_con_costo(c, &caminos);
+    Ok(ExitCode::SUCCESS)
+}
+
+// El par disjunto también: dos caminos, cada uno con su suma
+fn correr_disjuntos<W: Peso>(c: &Consulta<W>) -> Result<ExitCode, String> {
+    let sin_nodos = c.args.sin_nodos_comunes;
+    let (par, log) = caminos_disjuntos(&c.g, c.origen, c.destino(), sin_nodos)?;
+    imprimir_pasos(c.args, &log);
+    let Some(par) = par else {
+        println!(
+            "No hay dos caminos {} {} -> {}",
+            if sin_nodos {
+                "sin nodos en común"
+            } else {
+                "sin aristas en común"
+            },
+            c.labels[c.origen],
+            c.labels[c.destino()]
+        );
+        return Ok(ExitCode::from(3));
+    };
+    println!(
+        "Par de caminos disjuntos {} -> {}:",
+        c.labels[c.origen],
+        c.labels[c.destino()]
+    );
+    imprimir_con_costo(c, &par);
+    Ok(ExitCode::SUCCESS)
+}
+
+// El frente de Pareto: un camino por punto, con su costo y su recurso
+fn correr_pareto<W: Peso>(c: &Consulta<W>, recursos: &[W]) -> Result<ExitCode, String> {
+    let frente = frente_pareto(&c.g, recursos, c.origen, c.destino())?;
+    if frente.is_empty() {
+        return Ok(c.no_alcanzable());
+    }
+    println!(
+        "Frente de Pareto {} -> {} ({} caminos):",
+        c.labels[c.origen],
+        c.labels[c.destino()],
+        frente.len()
+    );
+    for (i, (camino, costo, recurso)) in frente.iter().enumerate() {
+        println!(
+            "  {}: {}   (suma de pesos = {}, recurso = {})",
+            i + 1,
+            texto_camino(&c.labels, camino),
+            costo,
+            recurso
+        );
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+// Con flujo se imprime lo que pasa por cada arista y el corte mínimo, no hay caminos
+fn correr_flujo<W: Peso>(c: &Consulta<W>, capacidades: &[W]) -> Result<ExitCode, String> {
+    let (g, labels) = (&c.g, &c.labels);
+    let flujo = match c.args.algoritmo {
+        Algoritmo::FlujoCosto => flujo_costo_minimo(g, capacidades, c.origen, c.destino()),
+        _ => flujo_maximo(g, capacidades, c.origen, c.destino()),
+    }?;
+    imprimir_pasos(c.args, &flujo.log);
+    let arista = |e: usize| {
+        let (u, v) = g.edge_endpoints(EdgeIndex::new(e)).unwrap();
+        format!(
+            "{} -> {}: {}/{}",
+            nodo(labels, u.index()),
+            nodo(labels, v.index()),
+            flujo.por_arista[e],
+            capacidades[e]
+        )
+    };
+    let (o, d) = (&labels[c.origen], &labels[c.destino()]);
+    match flujo.costo {
+        Some(costo) => println!(
+            "Flujo máximo {} -> {} = {} (costo = {}):",
+            o, d, flujo.valor, costo
+        ),
+        None => println!("Flujo máximo {} -> {} = {}:", o, d, flujo.valor),
+    }
+    for e in 0..g.edge_count() {
+        println!("  {}", arista(e));
+    }
+    println!();
+    let lado = (0..labels.len())
+        .filter(|&v| flujo.lado_origen[v])
+        .map(|v| nodo(labels, v))
+        .collect::<Vec<_>>()
+        .join(", ");
+    println!("Corte mínimo, lado del origen: {}", lado);
+    for &e in &flujo.corte {
+        println!("  {}", arista(e));
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+// Los árboles generadores tampoco dan caminos: se listan sus aristas
+fn correr_arbol<W: Peso>(c: &Consulta<W>) -> Result<ExitCode, String> {
+    let (g, labels, origen) = (&c.g, &c.labels, c.origen);
+    let aristas = match c.args.algoritmo {
+        Algoritmo::Prim => {
+            let (aristas, total, log) = prim(g)?;
+            imprimir_pasos(c.args, &log);
+            println!("Árbol generador mínimo (Prim), peso total = {}:", total);
+            aristas
+        }
+        Algoritmo::Kruskal => {
+            let (aristas, total, log) = kruskal(g)?;
+            imprimir_pasos(c.args, &log);
+            println!("Árbol generador mínimo (Kruskal), peso total = {}:", total);
+            aristas
+        }
+        _ => {
+            let (aristas, total, log) = arborescencia_minima(g, origen)?;
+            imprimir_pasos(c.args, &log);
+            println!(
+                "Arborescencia mínima desde {}, peso total = {}:",
+                nodo(labels, origen),
+                total
+            );
+            aristas
+        }
+    };
+    let dirigido = matches!(c.args.algoritmo, Algoritmo::Arborescencia);
+    let mut en_arbol = vec![false; labels.len()];
+    en_arbol[origen] = true;
+    for &id in &aristas {
+        let e = &g.raw_edges()[id];
+        let (u, v) = (e.source().index(), e.target().index());
+        en_arbol[v] = true;
+        println!(
+            "  {} {} {}   (w={})",
+            nodo(labels, u),
+            if dirigido { "->" } else { "-" },
+            nodo(labels, v),
+            e.weight
+        );
+    }
+    let fuera: Vec<String> = (0..labels.len())
+        .filter(|&v| !en_arbol[v])
+        .map(|v| nodo(labels, v))
+        .collect();
+    if dirigido && !fuera.is_empty() {
+        println!(
+            "  (no alcanzables desde {}: {})",
+            nodo(labels, origen),
+            fuera.join(", ")
+        );
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+// Un nodo como se imprime en toda la salida, `A(0)`
+fn nodo(labels: &[String], v: usize) -> String {
+    format!("{}({})", labels[v], v)
+}
+
+// Un camino como `A(0) -> B(1) -> ...`
+fn texto_camino(labels: &[String], camino: &[usize]) -> String {
+    camino
+        .iter()
+        .map(|&v| nodo(labels, v))
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+// La tabla con el valor de cada nodo y después los caminos al destino
+fn imprimir_resultado<W, V: Copy + std::fmt::Display>(
+    c: &Consulta<W>,
+    valor: &[Option<V>],
+    preds: &[Vec<usize>],
+    nota: &str,
+    nombres: &Nombres,
+) -> ExitCode {
+    imprimir_tabla(c, valor, nota, nombres);
+
+    let Some(total) = valor[c.destino()] else {
+        return c.no_alcanzable();
+    };
+
+    let caminos = reconstruir_caminos_limitado(preds, c.origen, c.destino(), c.args.max_caminos);
+    let cuantos = contar_caminos(preds, c.origen, c.destino());
+    imprimir_caminos(c, &caminos, cuantos, nombres, total);
+    ExitCode::SUCCESS
+}
+
+fn imprimir_tabla<W, V: std::fmt::Display>(
+    c: &Consulta<W>,
+    valor: &[Option<V>],
+    nota: &str,
+    nombres: &Nombres,
+) {
+    println!(
+        "{} desde {}{}:",
+        nombres.tabla,
+        nodo(&c.labels, c.origen),
+        nota
+    );
+    for (i, d) in valor.iter().enumerate() {
+        match d {
+            Some(d) => println!("  {} = {}", nodo(&c.labels, i), d),
+            None => println!("  {} = {}", nodo(&c.labels, i), nombres.sin_camino),
+        }
+    }
+    println!();
+}
+
+fn imprimir_caminos<W>(
+    c: &Consulta<W>,
+    caminos: &[Vec<usize>],
+    cuantos: Option<u128>,
+    nombres: &Nombres,
+    total: impl std::fmt::Display,
+) {
+    println!(
+        "{} {} -> {} ({} = {}):",
+        nombres.caminos,
+        c.labels[c.origen],
+        c.labels[c.destino()],
+        nombres.valor,
+        total
+    );
+    for (i, camino) in caminos.iter().enumerate() {
+        println!("  {}: {}", i + 1, texto_camino(&c.labels, camino));
+    }
+    match cuantos {
+        Some(n) if n > caminos.len() as u128 => {
+            println!("  (mostrando {} de {} caminos)", caminos.len(), n)
+        }
+        Some(_) => {}
+        None => println!(
+            "  (mostrando {} de más de {} caminos)",
+            caminos.len(),
+            u128::MAX
+        ),
+    }
+}
+
+// Caminos de distinto costo (yen, disjuntos), numerados y cada uno con su suma
+fn imprimir_con_costo<W: Peso>(c: &Consulta<W>, caminos: &[(Vec<usize>, W)]) {
+    for (i, (camino, costo)) in caminos.iter().enumerate() {
+        println!(
+            "  {}: {}   (suma de pesos = {})",
+            i + 1,
+            texto_camino(&c.labels, camino),
+            costo
+        );
+    }
+}
+
+fn imprimir_pasos(args: &Args, log: &[String]) {
+    if !args.pasos {
+        return;
+    }
+    println!("Paso a paso:");
+    for l in log {
+        println!("  {}", l);
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Args, String> {
+        parse_args(s.split_whitespace().map(String::from)).map(|a| a.expect("no es --help"))
+    }
+
+    #[test]
+    fn argumentos_basicos() {
+        let a = parse("g.txt --from A --to C --nodos 5 --pasos").unwrap();
+        assert_eq!(a.archivo.as_deref(), Some("g.txt"));
+        assert_eq!((a.from.as_str(), a.to.as_deref()), ("A", Some("C")));
+        assert_eq!(a.nodos, Some(5));
+        assert!(a.pasos);
+
+        let a = parse("- --from A --to B").unwrap();
+        assert!(a.archivo.is_none());
+        assert_eq!(a.nodos, None);
+    }
+
+    #[test]
+    fn argumentos_invalidos() {
+        assert!(parse("--to B").is_err());
+        assert!(parse("--from A --to B --nodos x").is_err());
+        assert!(parse("--from A --to B --raro").is_err());
+        assert!(parse("a.txt b.txt --from A --to B").is_err());
+    }
+
+    #[test]
+    fn lee_archivo_y_nodos() {
+        let a = parse("g.txt --from A --to B --max-caminos 2 --pasos").unwrap();
+        assert_eq!(a.archivo.as_deref(), Some("g.txt"));
+        assert_eq!((a.from.as_str(), a.to.as_deref()), ("A", Some("B")));
+        assert_eq!(a.max_caminos, 2);
+        assert!(a.pasos);
+        assert!(matches!(a.algoritmo, Algoritmo::Dijkstra));
+    }
+
+    #[test]
+    fn errores_de_argumentos() {
+        assert!(parse("--to B").is_err());
+        assert!(parse("--from A").is_err());
+        assert!(parse("--from A --to B --algoritmo nada").is_err());
+        assert!(parse("--from A --to B --k").is_err());
+        assert!(parse("a.txt b.txt --from A --to B").is_err());
+        assert!(parse("--from A --to B --algoritmo astar --evitar C").is_err());
+        assert!(parse("--from A --to B --heuristica cero").is_err());
+        assert!(parse("--from A --to B --algoritmo yen --escala 2").is_err());
+        assert!(parse("--from A --to B --k 2").is_err());
+        assert!(parse("--from A --to B --salida 8").is_err());
+        for t in ["NaN", "inf", "-inf"] {
+            let a = format!("--from A --to B --algoritmo horario --salida {}", t);
+            assert!(parse(&a).is_err());
+        }
+        assert!(parse("--from A --to B --algoritmo horario --salida 8.5").is_ok());
+        assert!(parse("--from A --to B --algoritmo astar --hasta-destino").is_err());
+        assert!(parse("--from A --to B --por C --hasta-destino").is_err());
+        assert!(parse("--from A --to B --evitar C --hasta-destino").is_ok());
+        assert!(parse("--from A --to B --cualquier-orden").is_err());
+        assert!(parse("--from A --to B --por C --cualquier-orden").is_ok());
+        for a in ["yen", "pareto"] {
+            assert!(parse(&format!("--from A --to B --algoritmo {} --pasos", a)).is_err());
+        }
+        assert!(parse("--from A --to B --por C --pasos").is_err());
+        assert!(parse("--from A --to B --presupuesto 5 --pasos").is_err());
+        assert!(parse("--from A --to B --algoritmo flujo --pasos").is_ok());
+        for a in [
+            "yen",
+            "pareto",
+            "giros",
+            "disjuntos",
+            "flujo",
+            "flujo-costo",
+        ] {
+            let a = format!("--from A --to B --algoritmo {} --max-caminos 2", a);
+            assert!(parse(&a).is_err());
+        }
+        assert!(parse("--from A --to B --por C --max-caminos 2").is_err());
+        assert!(parse("--from A --to B --algoritmo horario --max-caminos 2").is_ok());
+    }
+
+    #[test]
+    fn help_no_es_error() {
+        for h in ["-h", "--help", "--from A -h"] {
+            let a = parse_args(h.split_whitespace().map(String::from));
+            assert!(matches!(a, Ok(None)));
+        }
+    }
+
+    #[test]
+    fn codigos_de_salida() {
+        let correr = |argumentos: &str, texto: &str| {
+            resolver::<i64>(&parse(argumentos).unwrap(), texto.into())
+        };
+        let g = "nodos: A B C X\nA B 1\nB C 1\nA C 5";
+        assert_eq!(correr("--from A --to C", g), ExitCode::SUCCESS);
+        assert_eq!(correr("--from A --to X", g), ExitCode::from(3));
+        assert_eq!(correr("--from A --to Z", g), ExitCode::from(2));
+        assert_eq!(correr("--from A --to B", "A B x"), ExitCode::from(1));
+        assert_eq!(correr("--from A --to B", "A B -1"), ExitCode::from(1));
+        let bf = "--from A --to B --algoritmo bellman-ford";
+        assert_eq!(correr(bf, "A B -1"), ExitCode::SUCCESS);
+    }
+
+    #[test]
+    fn formatea_caminos_con_indice() {
+        let labels: Vec<String> = ["A", "B", "C"].map(String::from).into();
+        assert_eq!(texto_camino(&labels, &[0, 2, 1]), "A(0) -> C(2) -> B(1)");
+        assert_eq!(texto_camino(&labels, &[]), "");
+    }
+
+    #[test]
+    fn los_arboles_no_piden_destino() {
+        for a in ["prim", "kruskal", "arborescencia"] {
+            let args = parse(&format!("--from A --algoritmo {}", a)).unwrap();
+            assert!(args.to.is_none());
+        }
+    }
+}
@@ -1,33 +1,64 @@
+/// Colores para destacar aristas (por índice de arista) y nodos sobre el dibujo normal,
+/// por ejemplo los caminos mínimos o un ciclo negativo. `nodos` cambia el borde y `relleno` el
+/// fondo del nodo (lo usa la partición por origen). `etiquetas` reemplaza el peso que se escribe
+/// en la arista con ese índice (lo usa el flujo para `flujo/capacidad`). Con `arbol`, las aristas
+/// cuyo índice no está se dibujan atenuadas (salvo que tengan color en `aristas`).
+#[derive(Default)]
+pub struct Resaltado {
+    pub aristas: HashMap<usize, Color32>,
+    pub nodos: HashMap<usize, Color32>,
+    pub relleno: HashMap<usize, Color32>,
+    pub etiquetas: HashMap<usize, String>,
+    pub arbol: Option<HashSet<usize>>,
+}
+
+impl Resaltado {
+    /// Marca con `color` todas las aristas `u -> v`.
+    pub fn par(&mut self, g: &DiGraph<String, i32>, u: usize, v: usize, color: Color32) {
+        for e in g.edges_connecting(NodeIndex::new(u), NodeIndex::new(v)) {
+            self.aristas.insert(e.id().index(), color);
+        }
+    }
+
+    /// Marca con `color` las aristas consecutivas de `camino` y sus nodos. Entre paralelas se
+    /// marca la más barata (la que usan los solvers) que no tenga color todavía, así dos caminos
+    /// por paralelas distintas (el par disjunto) se ven los dos.
+    pub fn camino(&mut self, g: &DiGraph<String, i32>, camino: &[usize], color: Color32) {
+        for win in camino.windows(2) {
+            let paralelas = || g.edges_connecting(NodeIndex::new(win[0]), NodeIndex::new(win[1]));
+            let mas_barata = |e: &petgraph::graph::EdgeReference<i32>| (*e.weight(), e.id());
+            let libre = paralelas()
+                .filter(|e| !self.aristas.contains_key(&e.id().index()))
+                .min_by_key(mas_barata);
+            if let Some(e) = libre.or_else(|| paralelas().min_by_key(mas_barata)) {
+                self.aristas.insert(e.id().index(), color);
+            }
+        }
+        for &v in camino {
+            self.nodos.insert(v, color);
+        }
+    }
+}
+
 /// Igual que draw_graph pero permite desplazamiento (offset) para pan.
+#[allow(clippy::too_many_arguments)]
 pub fn draw_graph_offset(
     _ui: &egui::Ui,
     painter: &egui::Painter,
     rect: egui::Rect,
     g: &DiGraph<String, i32>,
-    labels: &Vec<String>,
+    labels: &[String],
     zoom: f32,
     mostrar_pesos: bool,
     offset: Vec2,
+    resaltado: &Resaltado,
 ) {
     let n = g.node_count();
     if n == 0 {
         return;
     }
 
-    // layout circular con offset
-    let center = rect.center() + offset;
-    let r = (rect.width().min(rect.height()) * 0.45 * zoom as f32).max(40.0);
-    let radio_nodo = (18.0 * zoom as f32).clamp(10.0, 30.0);
-
-    // posiciones de nodos
-    let mut pos: Vec<Pos2> = Vec::with_capacity(n);
-    for i in 0..n {
-        let ang = (i as f32) / (n as f32) * std::f32::consts::TAU;
-        pos.push(Pos2 {
-            x: center.x + r * ang.cos(),
-            y: center.y + r * ang.sin(),
-        });
-    }
+    let (pos, radio_nodo) = posiciones(rect, n, zoom, offset);
 
     // estilos
     let stroke_edge = Stroke {
@@ -39,6 +70,10 @@ pub fn draw_graph_offset(
         color: Color32::from_gray(90),
     };
     let color_peso = Color32::from_rgb(240, 240, 240);
+    let stroke_atenuada = Stroke {
+        width: 1.0,
+        color: Color32::from_gray(55),
+    };
 
     // conteo de paralelas
     let mut multi_count: HashMap<(usize, usize), usize> = HashMap::new();
@@ -52,7 +87,10 @@ pub fn draw_graph_offset(
     for e in g.edge_references() {
         let u = e.source().index();
         let v = e.target().index();
-        let w = *e.weight();
+        let texto = match resaltado.etiquetas.get(&e.id().index()) {
+            Some(t) => t.clone(),
+            None => e.weight().to_string(),
+        };
 
         let pu = pos[u];
         let pv = pos[v];
@@ -69,6 +107,20 @@ pub fn draw_graph_offset(
         let key = (u, v);
         let total = *multi_count.get(&key).unwrap_or(&1);
 
+        let atenuada = resaltado
+            .arbol
+            .as_ref()
+            .is_some_and(|a| !a.contains(&e.id().index()));
+        let (stroke_edge, stroke_arrow, color_peso) = match resaltado.aristas.get(&e.id().index()) {
+            Some(&color) => (
+                Stroke { width: 3.0, color },
+                Stroke { width: 3.0, color },
+                color_peso,
+            ),
+            None if atenuada => (stroke_atenuada, stroke_atenuada, Color32::from_gray(110)),
+            None => (stroke_edge, stroke_arrow, color_peso),
+        };
+
         if total == 1 {
             // ---- única arista: recta ----
             painter.line_segment([a, b], stroke_edge);
@@ -100,36 +152,13 @@ pub fn draw_graph_offset(
                     let sep = 12.0 * zoom;
                     mid += normal * sep;
                 }
-                painter.text(
-                    mid,
-                    Align2::CENTER_CENTER,
-                    format!("{}", w),
-                    font,
-                    color_peso,
-                );
+                painter.text(mid, Align2::CENTER_CENTER, texto, font, color_peso);
             }
         } else {
             // ---- múltiples aristas: curvas a lados opuestos ----
             let idx = *seen.entry(key).and_modify(|i| *i += 1).or_insert(0);
-
-            let side = if idx % 2 == 0 { 1.0 } else { -1.0 };
-            let tier = (idx / 2) as f32 + 1.0;
-
-            let dist = (b - a).length();
-            let base_curva = (dist * 0.20).clamp(16.0, 80.0);
-            let offset = side * tier * base_curva;
-
-            let push = (dist * 0.10).clamp(8.0, 32.0);
-            let ctrl1 = a + dir * push + normal * offset;
-            let ctrl2 = b - dir * push + normal * offset;
-
-            // polilínea de la Bezier
-            let samples = 24;
-            let mut pts: Vec<Pos2> = Vec::with_capacity(samples + 1);
-            for i in 0..=samples {
-                let t = i as f32 / samples as f32;
-                pts.push(bezier_point(a, ctrl1, ctrl2, b, t));
-            }
+            let (pts, offset) = puntos_curva(a, b, idx);
+            let samples = pts.len() - 1;
             painter.add(egui::Shape::line(pts.clone(), stroke_edge));
 
             // tangente = último tramo dibujado
@@ -153,7 +182,7 @@ pub fn draw_graph_offset(
             ));
 
             if mostrar_pesos {
-                let mut mid = bezier_point(a, ctrl1, ctrl2, b, 0.5);
+                let mut mid = pts[samples / 2];
                 let font = egui::FontId::proportional((12.0 * zoom).clamp(10.0, 18.0));
                 let opuesta = multi_count.get(&(v, u)).unwrap_or(&0) > &0;
                 if opuesta {
@@ -162,13 +191,7 @@ pub fn draw_graph_offset(
                 } else {
                     mid += normal * (offset.signum() * 0.10 * arrow_w);
                 }
-                painter.text(
-                    mid,
-                    Align2::CENTER_CENTER,
-                    format!("{}", w),
-                    font,
-                    color_peso,
-                );
+                painter.text(mid, Align2::CENTER_CENTER, texto, font, color_peso);
             }
         }
     }
@@ -183,7 +206,16 @@ pub fn draw_graph_offset(
 
     for i in 0..n {
         let p = pos[i];
+        let stroke_node = match resaltado.nodos.get(&i) {
+            Some(&color) => Stroke { width: 3.5, color },
+            None => stroke_node,
+        };
+        let fill_node = resaltado.relleno.get(&i).copied().unwrap_or(fill_node);
         painter.circle(p, radio_nodo, fill_node, stroke_node);
+        // con muchos nodos las etiquetas se tapan entre sí, solo se escriben las resaltadas
+        if n > MAX_ETIQUETAS && !resaltado.nodos.contains_key(&i) {
+            continue;
+        }
         painter.text(
             p,
             Align2::CENTER_CENTER,
@@ -206,9 +238,240 @@ pub fn draw_graph_offset(
 }
 
 use eframe::egui::{self, Align2, Color32, Pos2, Rounding, Stroke, Vec2};
-use petgraph::graph::DiGraph;
+use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+// arriba de esto no se escribe la etiqueta de cada nodo (no entran en el círculo y con miles de
+// nodos escribirlas es lo que más tarda por frame)
+const MAX_ETIQUETAS: usize = 200;
+
+// Layout circular con offset: posición de cada nodo y radio con el que se dibujan
+fn posiciones(rect: egui::Rect, n: usize, zoom: f32, offset: Vec2) -> (Vec<Pos2>, f32) {
+    let center = rect.center() + offset;
+    let r = (rect.width().min(rect.height()) * 0.45 * zoom).max(40.0);
+    let radio_nodo = (18.0 * zoom).clamp(10.0, 30.0);
+
+    let mut pos: Vec<Pos2> = Vec::with_capacity(n);
+    for i in 0..n {
+        let ang = (i as f32) / (n as f32) * std::f32::consts::TAU;
+        pos.push(Pos2 {
+            x: center.x + r * ang.cos(),
+            y: center.y + r * ang.sin(),
+        });
+    }
+    (pos, radio_nodo)
+}
+
+// Polilínea de la idx-ésima arista paralela de a a b (Bezier a lados alternos, cada par más
+// abierto) y cuánto se separa de la recta
+fn puntos_curva(a: Pos2, b: Pos2, idx: usize) -> (Vec<Pos2>, f32) {
+    let dir = (b - a).normalized();
+    let normal = Vec2::new(-dir.y, dir.x);
+
+    let side = if idx.is_multiple_of(2) { 1.0 } else { -1.0 };
+    let tier = (idx / 2) as f32 + 1.0;
+
+    let dist = (b - a).length();
+    let base_curva = (dist * 0.20).clamp(16.0, 80.0);
+    let offset = side * tier * base_curva;
+
+    let push = (dist * 0.10).clamp(8.0, 32.0);
+    let ctrl1 = a + dir * push + normal * offset;
+    let ctrl2 = b - dir * push + normal * offset;
+
+    let samples = 24;
+    let mut pts: Vec<Pos2> = Vec::with_capacity(samples + 1);
+    for i in 0..=samples {
+        let t = i as f32 / samples as f32;
+        pts.push(bezier_point(a, ctrl1, ctrl2, b, t));
+    }
+    (pts, offset)
+}
+
+/// Lo que hay bajo el puntero en el dibujo de [`draw_graph_offset`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Elemento {
+    Nodo(usize),
+    Arista(usize, usize),
+}
+
+/// Busca el nodo o arista en `punto`, con la misma geometría que [`draw_graph_offset`] (mismos
+/// `rect`, `zoom` y `offset`). Los nodos tienen prioridad sobre las aristas que pasan cerca.
+pub fn elemento_en(
+    rect: egui::Rect,
+    g: &DiGraph<String, i32>,
+    zoom: f32,
+    offset: Vec2,
+    punto: Pos2,
+) -> Option<Elemento> {
+    let n = g.node_count();
+    let (pos, radio_nodo) = posiciones(rect, n, zoom, offset);
+    if let Some(i) = (0..n).find(|&i| (punto - pos[i]).length() <= radio_nodo) {
+        return Some(Elemento::Nodo(i));
+    }
+
+    let mut multi_count: HashMap<(usize, usize), usize> = HashMap::new();
+    for e in g.edge_references() {
+        let key = (e.source().index(), e.target().index());
+        *multi_count.entry(key).or_insert(0) += 1;
+    }
+    let mut seen: HashMap<(usize, usize), usize> = HashMap::new();
+    // la arista más cercana a menos de 6 px
+    let mut mejor: Option<(f32, Elemento)> = None;
+    for e in g.edge_references() {
+        let (u, v) = (e.source().index(), e.target().index());
+        let dir = (pos[v] - pos[u]).normalized();
+        let margen = radio_nodo + 4.0;
+        let a = pos[u] + dir * margen;
+        let b = pos[v] - dir * margen;
+        let pts = if multi_count[&(u, v)] == 1 {
+            vec![a, b]
+        } else {
+            let idx = *seen.entry((u, v)).and_modify(|i| *i += 1).or_insert(0);
+            puntos_curva(a, b, idx).0
+        };
+        for tramo in pts.windows(2) {
+            let d = distancia_a_segmento(punto, tramo[0], tramo[1]);
+            if d <= 6.0 && mejor.is_none_or(|(m, _)| d < m) {
+                mejor = Some((d, Elemento::Arista(u, v)));
+            }
+        }
+    }
+    mejor.map(|(_, e)| e)
+}
+
+fn distancia_a_segmento(p: Pos2, a: Pos2, b: Pos2) -> f32 {
+    let ab = b - a;
+    let largo2 = ab.length_sq();
+    if largo2 == 0.0 {
+        return (p - a).length();
+    }
+    let t = ((p - a).dot(ab) / largo2).clamp(0.0, 1.0);
+    (p - (a + ab * t)).length()
+}
+
+/// Diagrama de dispersión del frente de Pareto, `puntos` son `(costo, recurso)`: costo en x,
+/// recurso en y. El punto `sel` se dibuja más grande y en otro color.
+pub fn draw_frente(
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    puntos: &[(i32, i32)],
+    sel: Option<usize>,
+) {
+    let (pos, area) = posiciones_frente(rect, puntos);
+    let eje = Stroke {
+        width: 1.0,
+        color: Color32::from_gray(150),
+    };
+    let color_texto = Color32::from_gray(200);
+    let font = egui::FontId::proportional(12.0);
+    painter.line_segment([area.left_bottom(), area.right_bottom()], eje);
+    painter.line_segment([area.left_bottom(), area.left_top()], eje);
+    painter.text(
+        area.right_bottom() + Vec2::new(0.0, 14.0),
+        Align2::RIGHT_CENTER,
+        "costo",
+        font.clone(),
+        color_texto,
+    );
+    painter.text(
+        area.left_top() - Vec2::new(0.0, 10.0),
+        Align2::LEFT_CENTER,
+        "recurso",
+        font.clone(),
+        color_texto,
+    );
+
+    // mínimos y máximos de cada eje junto a los extremos del frente
+    if let (Some(a), Some(b)) = (puntos.first(), puntos.last()) {
+        painter.text(
+            Pos2::new(pos[0].x, area.bottom() + 4.0),
+            Align2::CENTER_TOP,
+            a.0.to_string(),
+            font.clone(),
+            color_texto,
+        );
+        painter.text(
+            Pos2::new(pos[pos.len() - 1].x, area.bottom() + 4.0),
+            Align2::CENTER_TOP,
+            b.0.to_string(),
+            font.clone(),
+            color_texto,
+        );
+        painter.text(
+            Pos2::new(area.left() - 4.0, pos[0].y),
+            Align2::RIGHT_CENTER,
+            a.1.to_string(),
+            font.clone(),
+            color_texto,
+        );
+        painter.text(
+            Pos2::new(area.left() - 4.0, pos[pos.len() - 1].y),
+            Align2::RIGHT_CENTER,
+            b.1.to_string(),
+            font,
+            color_texto,
+        );
+    }
+
+    // escalera entre puntos vecinos, así se ve que entre medio no hay nada alcanzable
+    for par in pos.windows(2) {
+        let esquina = Pos2::new(par[1].x, par[0].y);
+        painter.line_segment([par[0], esquina], eje);
+        painter.line_segment([esquina, par[1]], eje);
+    }
+    for (i, &p) in pos.iter().enumerate() {
+        if Some(i) == sel {
+            painter.circle_filled(p, 7.0, Color32::from_rgb(255, 170, 60));
+        } else {
+            painter.circle_filled(p, 4.5, Color32::from_rgb(70, 130, 220));
+        }
+    }
+
+    painter.rect(rect, Rounding::same(8.0), Color32::TRANSPARENT, eje);
+}
+
+/// El punto de [`draw_frente`] bajo `punto` (a menos de 10 px), con los mismos `rect` y `puntos`.
+pub fn punto_frente_en(rect: egui::Rect, puntos: &[(i32, i32)], punto: Pos2) -> Option<usize> {
+    let (pos, _) = posiciones_frente(rect, puntos);
+    pos.iter()
+        .enumerate()
+        .map(|(i, p)| (i, (punto - *p).length()))
+        .filter(|&(_, d)| d <= 10.0)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
+
+// Posición de cada punto del frente y el área de los ejes (el rect menos márgenes para los
+// textos). Si todos los puntos tienen el mismo valor en un eje quedan centrados en ese eje
+fn posiciones_frente(rect: egui::Rect, puntos: &[(i32, i32)]) -> (Vec<Pos2>, egui::Rect) {
+    let area = egui::Rect::from_min_max(
+        rect.min + Vec2::new(48.0, 28.0),
+        rect.max - Vec2::new(16.0, 36.0),
+    );
+    let rango = |vals: &mut dyn Iterator<Item = i32>| {
+        let (mut lo, mut hi) = (i32::MAX, i32::MIN);
+        for v in vals {
+            lo = lo.min(v);
+            hi = hi.max(v);
+        }
+        (lo as f32, hi as f32)
+    };
+    let (x0, x1) = rango(&mut puntos.iter().map(|p| p.0));
+    let (y0, y1) = rango(&mut puntos.iter().map(|p| p.1));
+    let escalar = |v: f32, lo: f32, hi: f32| if hi > lo { (v - lo) / (hi - lo) } else { 0.5 };
+    let pos = puntos
+        .iter()
+        .map(|&(c, r)| {
+            Pos2::new(
+                area.left() + escalar(c as f32, x0, x1) * area.width(),
+                area.bottom() - escalar(r as f32, y0, y1) * area.height(),
+            )
+        })
+        .collect();
+    (pos, area)
+}
 
 fn bezier_point(p0: Pos2, p1: Pos2, p2: Pos2, p3: Pos2, t: f32) -> Pos2 {
     let u = 1.0 - t;
@@ -1,17 +1,18 @@
-use petgraph::graph::DiGraph;
+use crate::peso::Peso;
+use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashSet};
 
-// Estructura para la cola de prioridad
+// Estructura para la cola de prioridad, A* y las otras variantes también la usan
 #[derive(Clone, Eq, PartialEq)]
-struct Entrada {
-    dist: i32,
-    node: usize,
+pub(crate) struct Entrada<W> {
+    pub(crate) dist: W,
+    pub(crate) node: usize,
 }
 // inicialzadores de orden para BinaryHeap (min-heap), por defecto es max-heap osea
 // encontrar el mayor pero aqui se cambia al menor
-impl Ord for Entrada {
+impl<W: Ord> Ord for Entrada<W> {
     fn cmp(&self, other: &Self) -> Ordering {
         other
             .dist
@@ -19,109 +20,819 @@ impl Ord for Entrada {
             .then_with(|| self.node.cmp(&other.node))
     }
 }
-impl PartialOrd for Entrada {
+impl<W: Ord> PartialOrd for Entrada<W> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
+/// Un paso de Dijkstra. Los índices son de nodo y `heap` es el tamaño de la cola de prioridad
+/// justo después del paso, sirve para animaciones, pruebas o estadísticas sin parsear texto.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Evento<W> {
+    /// `dist[origen] = 0`, el resto en ∞.
+    Inicio { origen: usize, heap: usize },
+    /// `nodo` es un origen que arranca con `dist` distinto de 0 (Dijkstra con varios orígenes).
+    Origen { nodo: usize, dist: W, heap: usize },
+    /// Se saca `nodo` de la cola y queda fijo con `dist`.
+    Selecciona { nodo: usize, dist: W, heap: usize },
+    /// Se saca `nodo` de la cola pero ya estaba fijo (entrada vieja), se descarta.
+    YaVisitado { nodo: usize, dist: W, heap: usize },
+    /// Primera vez que se llega a `v`, por la arista `u -> v` de peso `w`.
+    Descubre {
+        u: usize,
+        v: usize,
+        w: W,
+        dist: W,
+        heap: usize,
+    },
+    /// Se encontró un camino más corto a `v` pasando por `u`.
+    Mejora {
+        u: usize,
+        v: usize,
+        w: W,
+        antes: W,
+        despues: W,
+        heap: usize,
+    },
+    /// Camino empatado a `v` por `u`, se agrega `u` como otro predecesor.
+    Empate {
+        u: usize,
+        v: usize,
+        dist: W,
+        heap: usize,
+    },
+    /// Se fijó el destino en una búsqueda con parada temprana
+    /// ([`dijkstra_eventos_hasta`]), no se sigue expandiendo.
+    DestinoFijado { nodo: usize, dist: W, heap: usize },
+    /// Terminó el algoritmo, `alcanzados` nodos tienen distancia finita (si paró en el destino
+    /// algunas de esas distancias son provisionales).
+    Fin { alcanzados: usize },
+}
+
+// Los textos son los que mostraba el log de siempre, la interfaz los sigue usando tal cual
+impl<W: std::fmt::Display> std::fmt::Display for Evento<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Evento::Inicio { origen, .. } => {
+                write!(f, "Inicializo dist[{}]=0, resto = ∞", origen)
+            }
+            Evento::Origen { nodo, dist, .. } => {
+                write!(f, "Inicializo dist[{}]={} (origen con desfase)", nodo, dist)
+            }
+            Evento::Selecciona { nodo, dist, .. } => {
+                write!(f, "Selecciono u={} con dist={}", nodo, dist)
+            }
+            Evento::YaVisitado { nodo, dist, .. } => {
+                write!(f, "  Descarto u={} con dist={} (ya visitado)", nodo, dist)
+            }
+            Evento::Descubre { u, v, w, dist, .. } => {
+                write!(f, "  Trazo ({} -> {}, w={}): dist[{}]={}", u, v, w, v, dist)
+            }
+            Evento::Mejora {
+                u,
+                v,
+                w,
+                antes,
+                despues,
+                ..
+            } => write!(
+                f,
+                "  Mejora ({} -> {}, w={}): dist[{}] {}→{}",
+                u, v, w, v, antes, despues
+            ),
+            Evento::Empate { u, v, .. } => {
+                write!(f, "  Empate óptimo hacia {}: también via {}", v, u)
+            }
+            Evento::DestinoFijado { nodo, dist, heap } => write!(
+                f,
+                "Destino {} fijado con dist={}, termino antes (quedan {} en la cola)",
+                nodo, dist, heap
+            ),
+            Evento::Fin { alcanzados } => {
+                write!(f, "Fin: {} nodos alcanzados", alcanzados)
+            }
+        }
+    }
+}
+
+/// Convierte los eventos al log de texto que muestra la interfaz, una línea por evento.
+/// Sirve para cualquier traza que se pueda mostrar, no solo [`Evento`].
+pub fn formatear_log<E: std::fmt::Display>(eventos: &[E]) -> Vec<String> {
+    eventos.iter().map(|e| e.to_string()).collect()
+}
+
+/// Lo que devuelven los solvers de un origen: `(dist, preds, traza)`, con la traza como texto o
+/// como eventos. `dist[v]` es `None` si `v` no es alcanzable y `preds[v]` son los predecesores que
+/// empatan en el óptimo.
+pub type Resultado<V, T = String> = (Vec<Option<V>>, Vec<Vec<usize>>, Vec<T>);
+
 // Algoritmo, se busca el menor, si es igual, se agrega como otro predecesor y al final se compara para encontrar el de menor peso
-// TOdo eso devuelve??? Si xd, devuelve distancias, predecesores y un log detallado, el log es solo para la interfaz y algunas pruebas.
-pub fn dijkstra_detallado(
-    g: &DiGraph<String, i32>,
+/// Dijkstra desde `origen` sobre todo el grafo alcanzable, con la traza como [`Evento`]s.
+///
+/// Devuelve `(dist, preds, eventos)`:
+/// - `dist[v]`: distancia mínima de `origen` a `v`, `None` si no es alcanzable.
+/// - `preds[v]`: todos los predecesores de `v` que empatan en el óptimo (DAG de caminos mínimos).
+/// - `eventos`: cada paso del algoritmo en orden.
+///
+/// Devuelve `Err` si encuentra un peso negativo (para eso está
+/// [`bellman_ford_detallado`](crate::bellman_ford::bellman_ford_detallado)) o si alguna distancia
+/// se desborda en el tipo `W`. Entra en pánico si `origen` no es un nodo del grafo.
+pub fn dijkstra_eventos<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+) -> Result<Resultado<W, Evento<W>>, String> {
+    let (dist, preds, _, eventos) =
+        dijkstra_interno(g, &[(origen, W::cero())], None, &|_, _| true)?;
+    Ok((dist, preds, eventos))
+}
+
+/// Igual que [`dijkstra_eventos`] pero para apenas sale `destino` de la cola.
+///
+/// `dist` y `preds` de `destino` y de todos los nodos en sus caminos mínimos quedan exactamente
+/// como en la corrida completa, así que [`reconstruir_todos_caminos`] da los mismos caminos
+/// empatados: antes de parar se sacan también los nodos que están a la misma distancia que el
+/// destino, por si llegan a él con aristas de peso 0. Las distancias de los demás nodos pueden ser
+/// provisionales o faltar.
+pub fn dijkstra_eventos_hasta<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+    destino: usize,
+) -> Result<Resultado<W, Evento<W>>, String> {
+    let (dist, preds, _, eventos) =
+        dijkstra_interno(g, &[(origen, W::cero())], Some(destino), &|_, _| true)?;
+    Ok((dist, preds, eventos))
+}
+
+/// Restricciones de una consulta de caminos, para cierres o enlaces caídos sin reconstruir el
+/// grafo. Con [`Default`] no hay ninguna y el resultado es el de [`dijkstra_eventos`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpcionesConsulta {
+    /// Nodos por los que no se puede pasar ni llegar.
+    pub nodos_bloqueados: HashSet<usize>,
+    /// Aristas `(u, v)` que no se pueden usar, bloquea también las paralelas entre `u` y `v`.
+    pub aristas_bloqueadas: HashSet<(usize, usize)>,
+    /// `Some(destino)` para parar apenas se fija, como [`dijkstra_eventos_hasta`].
+    pub hasta: Option<usize>,
+}
+
+impl OpcionesConsulta {
+    /// Si la arista `u -> v` se puede usar con estas restricciones.
+    pub fn permite(&self, u: usize, v: usize) -> bool {
+        !self.nodos_bloqueados.contains(&v) && !self.aristas_bloqueadas.contains(&(u, v))
+    }
+
+    /// Bloquea `v` si estaba libre y lo libera si estaba bloqueado.
+    pub fn alternar_nodo(&mut self, v: usize) {
+        if !self.nodos_bloqueados.remove(&v) {
+            self.nodos_bloqueados.insert(v);
+        }
+    }
+
+    /// Igual que [`alternar_nodo`](Self::alternar_nodo) para la arista `u -> v`.
+    pub fn alternar_arista(&mut self, u: usize, v: usize) {
+        if !self.aristas_bloqueadas.remove(&(u, v)) {
+            self.aristas_bloqueadas.insert((u, v));
+        }
+    }
+}
+
+/// Igual que [`dijkstra_eventos`] pero respetando las restricciones de `opciones`: los nodos y
+/// aristas bloqueados quedan fuera como si no estuvieran en el grafo. Devuelve `Err` si el
+/// propio `origen` está bloqueado.
+pub fn dijkstra_eventos_con<W: Peso>(
+    g: &DiGraph<String, W>,
     origen: usize,
-) -> (Vec<Option<i32>>, Vec<Vec<usize>>, Vec<String>) {
+    opciones: &OpcionesConsulta,
+) -> Result<Resultado<W, Evento<W>>, String> {
+    if opciones.nodos_bloqueados.contains(&origen) {
+        return Err(format!("El origen {} está bloqueado", origen));
+    }
+    let (dist, preds, _, eventos) =
+        dijkstra_interno(g, &[(origen, W::cero())], opciones.hasta, &|u, v| {
+            opciones.permite(u, v)
+        })?;
+    Ok((dist, preds, eventos))
+}
+
+/// Igual que [`dijkstra_eventos_con`] pero con la traza ya formateada como texto.
+pub fn dijkstra_detallado_con<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+    opciones: &OpcionesConsulta,
+) -> Result<Resultado<W>, String> {
+    let (dist, preds, eventos) = dijkstra_eventos_con(g, origen, opciones)?;
+    Ok((dist, preds, formatear_log(&eventos)))
+}
+
+/// Lo que devuelven los solvers multiorigen: `(dist, preds, fuente, traza)`, como [`Resultado`]
+/// pero con el origen que atiende a cada nodo.
+pub type ResultadoMultiorigen<V, T = String> =
+    (Vec<Option<V>>, Vec<Vec<usize>>, Vec<Option<usize>>, Vec<T>);
+
+/// Dijkstra desde varios orígenes a la vez (por ejemplo depósitos), cada uno con un desfase
+/// inicial `(nodo, desfase)`: es como si hubiera un super-origen con una arista de peso
+/// `desfase` a cada uno.
+///
+/// Devuelve `(dist, preds, fuente, eventos)`, con `dist`/`preds` como en [`dijkstra_eventos`] y
+/// `fuente[v]` el origen (índice de nodo) que atiende a `v` al menor costo, `None` si ninguno
+/// llega: la partición de Voronoi del grafo. Si dos orígenes empatan gana el que aparece primero
+/// en `origenes`, incluso si el empatado es otro origen (un depósito puede quedar atendido por
+/// otro al mismo costo); con aristas de peso 0 el empate puede resolverse distinto, porque un
+/// empate que llega a un nodo ya fijo se agrega a `preds` pero ya no cambia su `fuente`.
+/// Los caminos de `fuente[v]` a `v` salen con [`iter_caminos`]`(&preds, fuente[v], v)`. Un
+/// origen repetido se queda con su menor desfase.
+pub fn dijkstra_eventos_multiorigen<W: Peso>(
+    g: &DiGraph<String, W>,
+    origenes: &[(usize, W)],
+) -> Result<ResultadoMultiorigen<W, Evento<W>>, String> {
+    let (dist, preds, fuente, eventos) = dijkstra_interno(g, origenes, None, &|_, _| true)?;
+    let fuente = fuente
+        .into_iter()
+        .map(|f| f.map(|pos| origenes[pos].0))
+        .collect();
+    Ok((dist, preds, fuente, eventos))
+}
+
+/// Igual que [`dijkstra_eventos_multiorigen`] pero con la traza ya formateada como texto.
+pub fn dijkstra_multiorigen<W: Peso>(
+    g: &DiGraph<String, W>,
+    origenes: &[(usize, W)],
+) -> Result<ResultadoMultiorigen<W>, String> {
+    let (dist, preds, fuente, eventos) = dijkstra_eventos_multiorigen(g, origenes)?;
+    Ok((dist, preds, fuente, formatear_log(&eventos)))
+}
+
+// Lo que devuelve dijkstra_interno: dist, preds, fuente (posición en `origenes` del origen que
+// atiende a cada nodo) y eventos
+pub(crate) type Corrida<W> = ResultadoMultiorigen<W, Evento<W>>;
+
+// `permitida(u, v)` dice si se puede usar la arista u -> v, así Yen puede "borrar" nodos y
+// aristas sin copiar el grafo
+pub(crate) fn dijkstra_interno<W: Peso>(
+    g: &DiGraph<String, W>,
+    origenes: &[(usize, W)],
+    destino: Option<usize>,
+    permitida: &dyn Fn(usize, usize) -> bool,
+) -> Result<Corrida<W>, String> {
     let n = g.node_count();
-    let mut dist: Vec<Option<i32>> = vec![None; n];
+    let mut dist: Vec<Option<W>> = vec![None; n];
     let mut preds: Vec<Vec<usize>> = vec![vec![]; n];
+    let mut fuente: Vec<Option<usize>> = vec![None; n];
     let mut visitado = vec![false; n];
-    let mut heap: BinaryHeap<Entrada> = BinaryHeap::new();
-    let mut log: Vec<String> = vec![];
+    let mut heap: BinaryHeap<Entrada<W>> = BinaryHeap::new();
+    let mut eventos: Vec<Evento<W>> = vec![];
 
-    dist[origen] = Some(0);
-    heap.push(Entrada {
-        dist: 0,
-        node: origen,
-    });
-    log.push(format!("Inicializo dist[{}]=0, resto = ∞", origen));
+    for (pos, &(origen, desfase)) in origenes.iter().enumerate() {
+        if dist[origen].is_some_and(|d| d <= desfase) {
+            continue;
+        }
+        dist[origen] = Some(desfase);
+        fuente[origen] = Some(pos);
+        heap.push(Entrada {
+            dist: desfase,
+            node: origen,
+        });
+        // con un solo origen sin desfase la traza queda como siempre
+        eventos.push(if desfase == W::cero() {
+            Evento::Inicio {
+                origen,
+                heap: heap.len(),
+            }
+        } else {
+            Evento::Origen {
+                nodo: origen,
+                dist: desfase,
+                heap: heap.len(),
+            }
+        });
+    }
 
+    // distancia del destino una vez fijo; después solo se sacan los que están a esa misma
+    // distancia, que todavía pueden llegar a él por aristas de peso 0
+    let mut fijado: Option<W> = None;
     while let Some(Entrada { dist: d_u, node: u }) = heap.pop() {
+        if fijado.is_some_and(|d| d_u > d) {
+            break;
+        }
         if visitado[u] {
+            eventos.push(Evento::YaVisitado {
+                nodo: u,
+                dist: d_u,
+                heap: heap.len(),
+            });
             continue;
         }
         visitado[u] = true;
-        log.push(format!("Selecciono u={} con dist={}", u, d_u));
+        eventos.push(Evento::Selecciona {
+            nodo: u,
+            dist: d_u,
+            heap: heap.len(),
+        });
+        // los predecesores empatados con w > 0 ya salieron de la cola; los de w = 0 pueden estar
+        // todavía en ella con la misma distancia y se agregan como empates al salir
+        if destino == Some(u) {
+            eventos.push(Evento::DestinoFijado {
+                nodo: u,
+                dist: d_u,
+                heap: heap.len(),
+            });
+            fijado = Some(d_u);
+            continue;
+        }
 
         for e in g.edges(petgraph::prelude::NodeIndex::new(u)) {
             let v = e.target().index();
             let w = *e.weight();
+            if w < W::cero() {
+                return Err(format!(
+                    "Peso negativo en la arista {} -> {} (w={}), usa Bellman-Ford",
+                    u, v, w
+                ));
+            }
+            if !permitida(u, v) {
+                continue;
+            }
+            // un nodo fijo solo puede recibir empates por aristas de peso 0; su fuente ya no cambia
             if visitado[v] {
+                if d_u.sumar(w) == dist[v] && !preds[v].contains(&u) && !es_ancestro(&preds, v, u) {
+                    preds[v].push(u);
+                    eventos.push(Evento::Empate {
+                        u,
+                        v,
+                        dist: d_u,
+                        heap: heap.len(),
+                    });
+                }
                 continue;
             }
 
-            let alt = d_u.saturating_add(w);
+            let alt = d_u.sumar(w).ok_or_else(|| {
+                format!(
+                    "Desborde: dist[{}]={} + w={} ({} -> {}) no cabe en el tipo de peso",
+                    u, d_u, w, u, v
+                )
+            })?;
             match dist[v] {
                 None => {
                     dist[v] = Some(alt);
                     preds[v].clear();
                     preds[v].push(u);
+                    fuente[v] = fuente[u];
                     heap.push(Entrada { dist: alt, node: v });
-                    log.push(format!(
-                        "  Trazo ({} -> {}, w={}): dist[{}]={}",
-                        u, v, w, v, alt
-                    ));
+                    eventos.push(Evento::Descubre {
+                        u,
+                        v,
+                        w,
+                        dist: alt,
+                        heap: heap.len(),
+                    });
                 }
                 Some(curr) if alt < curr => {
                     dist[v] = Some(alt);
                     preds[v].clear();
                     preds[v].push(u);
+                    fuente[v] = fuente[u];
                     heap.push(Entrada { dist: alt, node: v });
-                    log.push(format!(
-                        "  Mejora ({} -> {}, w={}): dist[{}] {}→{}",
-                        u, v, w, v, curr, alt
-                    ));
+                    eventos.push(Evento::Mejora {
+                        u,
+                        v,
+                        w,
+                        antes: curr,
+                        despues: alt,
+                        heap: heap.len(),
+                    });
                 }
-                Some(curr) if alt == curr => {
-                    if !preds[v].contains(&u) {
-                        preds[v].push(u);
-                        log.push(format!("  Empate óptimo hacia {}: también via {}", v, u));
-                    }
+                // con varios orígenes, en empate se queda el que aparece primero en la lista
+                Some(curr) if alt == curr && !preds[v].contains(&u) => {
+                    preds[v].push(u);
+                    fuente[v] = fuente[v].min(fuente[u]);
+                    eventos.push(Evento::Empate {
+                        u,
+                        v,
+                        dist: alt,
+                        heap: heap.len(),
+                    });
                 }
                 _ => {}
             }
         }
     }
 
-    (dist, preds, log)
+    eventos.push(Evento::Fin {
+        alcanzados: dist.iter().filter(|d| d.is_some()).count(),
+    });
+    Ok((dist, preds, fuente, eventos))
+}
+
+// Si `a` se alcanza desde `x` siguiendo preds hacia atrás (o es el mismo nodo): agregar `x` como
+// predecesor de `a` armaría un ciclo. Sirve para registrar empates hacia nodos ya fijos, que
+// aparecen con aristas que no empeoran el camino (peso 0, capacidad de sobra, probabilidad 1)
+pub(crate) fn es_ancestro(preds: &[Vec<usize>], a: usize, x: usize) -> bool {
+    let mut visto = vec![false; preds.len()];
+    let mut pila = vec![x];
+    while let Some(u) = pila.pop() {
+        if u == a {
+            return true;
+        }
+        if !std::mem::replace(&mut visto[u], true) {
+            pila.extend(&preds[u]);
+        }
+    }
+    false
+}
+
+// TOdo eso devuelve??? Si xd, devuelve distancias, predecesores y un log detallado, el log es solo para la interfaz y algunas pruebas.
+/// Igual que [`dijkstra_eventos`] pero con la traza ya formateada como texto.
+pub fn dijkstra_detallado<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+) -> Result<Resultado<W>, String> {
+    let (dist, preds, eventos) = dijkstra_eventos(g, origen)?;
+    Ok((dist, preds, formatear_log(&eventos)))
+}
+
+/// Igual que [`dijkstra_eventos_hasta`] pero con la traza ya formateada como texto.
+pub fn dijkstra_detallado_hasta<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+    destino: usize,
+) -> Result<Resultado<W>, String> {
+    let (dist, preds, eventos) = dijkstra_eventos_hasta(g, origen, destino)?;
+    Ok((dist, preds, formatear_log(&eventos)))
 }
 
 // Generar el output de los caminos minimos que se muestra en la interfaz :good:
+/// Reconstruye todos los caminos mínimos de `origen` a `destino` a partir de los `preds`
+/// que devuelve [`dijkstra_detallado`]. Cada camino va de `origen` a `destino` (índices de nodo).
+///
+/// Si `destino` no es alcanzable devuelve un vector vacío. En grafos con muchos empates la
+/// cantidad de caminos crece exponencialmente, para esos casos están [`iter_caminos`],
+/// [`reconstruir_caminos_limitado`] y [`contar_caminos`].
 pub fn reconstruir_todos_caminos(
-    preds: &Vec<Vec<usize>>,
+    preds: &[Vec<usize>],
     origen: usize,
     destino: usize,
 ) -> Vec<Vec<usize>> {
-    let mut actual = vec![destino];
-    let mut res: Vec<Vec<usize>> = vec![];
-    fn dfs(
-        preds: &Vec<Vec<usize>>,
-        u: usize,
-        origen: usize,
-        camino: &mut Vec<usize>,
-        out: &mut Vec<Vec<usize>>,
-    ) {
-        if u == origen {
-            let mut c = camino.clone();
-            c.reverse();
-            out.push(c);
-            return;
+    iter_caminos(preds, origen, destino).collect()
+}
+
+/// Como [`reconstruir_todos_caminos`] pero se queda con los primeros `max_caminos`.
+pub fn reconstruir_caminos_limitado(
+    preds: &[Vec<usize>],
+    origen: usize,
+    destino: usize,
+    max_caminos: usize,
+) -> Vec<Vec<usize>> {
+    iter_caminos(preds, origen, destino)
+        .take(max_caminos)
+        .collect()
+}
+
+/// Iterador perezoso sobre los caminos mínimos de `origen` a `destino`, en el mismo orden que
+/// [`reconstruir_todos_caminos`]. Genera un camino a la vez con una pila propia (sin
+/// recursión), así que sirve aunque haya millones de caminos o sean muy largos.
+pub fn iter_caminos(preds: &[Vec<usize>], origen: usize, destino: usize) -> CaminosMinimos<'_> {
+    CaminosMinimos {
+        preds,
+        origen,
+        pila: vec![(destino, 0)],
+    }
+}
+
+/// Iterador que devuelve [`iter_caminos`].
+pub struct CaminosMinimos<'a> {
+    preds: &'a [Vec<usize>],
+    origen: usize,
+    // camino actual desde el destino hacia atrás, con el próximo predecesor a probar de cada nodo
+    pila: Vec<(usize, usize)>,
+}
+
+impl Iterator for CaminosMinimos<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        while let Some(&mut (u, ref mut i)) = self.pila.last_mut() {
+            if u == self.origen {
+                let camino = self.pila.iter().rev().map(|&(v, _)| v).collect();
+                self.pila.pop();
+                return Some(camino);
+            }
+            match self.preds[u].get(*i) {
+                Some(&p) => {
+                    *i += 1;
+                    self.pila.push((p, 0));
+                }
+                None => {
+                    self.pila.pop();
+                }
+            }
         }
-        for &p in &preds[u] {
-            camino.push(p);
-            dfs(preds, p, origen, camino, out);
-            camino.pop();
+        None
+    }
+}
+
+/// Suma de pesos de `camino` (una lista de nodos como las que devuelven los solvers), tomando la
+/// arista más barata entre cada par de nodos consecutivos si hay paralelas. Devuelve `Err` si
+/// falta alguna arista o si la suma se desborda.
+pub fn costo_camino<W: Peso>(g: &DiGraph<String, W>, camino: &[usize]) -> Result<W, String> {
+    let mut total = W::cero();
+    for par in camino.windows(2) {
+        let (u, v) = (par[0], par[1]);
+        let w = g
+            .edges_connecting(NodeIndex::new(u), NodeIndex::new(v))
+            .map(|e| *e.weight())
+            .min()
+            .ok_or_else(|| format!("No hay arista {} -> {} en el grafo", u, v))?;
+        total = total.sumar(w).ok_or_else(|| {
+            format!(
+                "Desborde: el costo de un camino no cabe en el tipo de peso ({} + {})",
+                total, w
+            )
+        })?;
+    }
+    Ok(total)
+}
+
+/// Cuenta los caminos mínimos de `origen` a `destino` sin generarlos (programación dinámica
+/// sobre `preds`, lineal en su tamaño). Devuelve `None` si son más de `u128::MAX`.
+pub fn contar_caminos(preds: &[Vec<usize>], origen: usize, destino: usize) -> Option<u128> {
+    // cuenta[v] = caminos de origen a v; se calcula en post-orden con una pila explícita
+    let mut cuenta: Vec<Option<u128>> = vec![None; preds.len()];
+    cuenta[origen] = Some(1);
+    let mut pila = vec![destino];
+    while let Some(&u) = pila.last() {
+        if cuenta[u].is_some() {
+            pila.pop();
+            continue;
         }
+        let pendientes: Vec<usize> = preds[u]
+            .iter()
+            .copied()
+            .filter(|&p| cuenta[p].is_none())
+            .collect();
+        if pendientes.is_empty() {
+            let mut total: u128 = 0;
+            for &p in &preds[u] {
+                total = total.checked_add(cuenta[p].expect("ya calculado"))?;
+            }
+            cuenta[u] = Some(total);
+            pila.pop();
+        } else {
+            pila.extend(pendientes);
+        }
+    }
+    cuenta[destino]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grafo::de_texto;
+
+    #[test]
+    fn la_traza_sale_como_eventos() {
+        // petgraph recorre las aristas de un nodo de la última agregada a la primera
+        let g = de_texto::<i64>("A B 2\nA C 5\nB C 1");
+        let (dist, _, eventos) = dijkstra_eventos(&g, 0).unwrap();
+        assert_eq!(dist, vec![Some(0), Some(2), Some(3)]);
+        assert_eq!(
+            eventos,
+            vec![
+                Evento::Inicio { origen: 0, heap: 1 },
+                Evento::Selecciona {
+                    nodo: 0,
+                    dist: 0,
+                    heap: 0
+                },
+                Evento::Descubre {
+                    u: 0,
+                    v: 2,
+                    w: 5,
+                    dist: 5,
+                    heap: 1
+                },
+                Evento::Descubre {
+                    u: 0,
+                    v: 1,
+                    w: 2,
+                    dist: 2,
+                    heap: 2
+                },
+                Evento::Selecciona {
+                    nodo: 1,
+                    dist: 2,
+                    heap: 1
+                },
+                Evento::Mejora {
+                    u: 1,
+                    v: 2,
+                    w: 1,
+                    antes: 5,
+                    despues: 3,
+                    heap: 2
+                },
+                Evento::Selecciona {
+                    nodo: 2,
+                    dist: 3,
+                    heap: 1
+                },
+                Evento::YaVisitado {
+                    nodo: 2,
+                    dist: 5,
+                    heap: 0
+                },
+                Evento::Fin { alcanzados: 3 },
+            ]
+        );
+        let (_, _, log) = dijkstra_detallado(&g, 0).unwrap();
+        assert_eq!(log, formatear_log(&eventos));
+        assert_eq!(log[5], "  Mejora (1 -> 2, w=1): dist[2] 5→3");
+    }
+
+    #[test]
+    fn hasta_no_expande_mas_alla_del_destino() {
+        let g = de_texto::<i64>("A B 1\nB C 1\nC D 1");
+        let (dist, _, eventos) = dijkstra_eventos_hasta(&g, 0, 1).unwrap();
+        assert_eq!(dist, vec![Some(0), Some(1), None, None]);
+        assert_eq!(eventos.last(), Some(&Evento::Fin { alcanzados: 2 }));
+    }
+
+    #[test]
+    fn hasta_con_destino_inalcanzable_recorre_todo() {
+        let g = de_texto::<i64>("nodos: A B C T\nA B 1\nB C 1");
+        let (dist, preds, eventos) = dijkstra_eventos_hasta(&g, 0, 3).unwrap();
+        assert_eq!(dist, vec![Some(0), Some(1), Some(2), None]);
+        assert!(reconstruir_todos_caminos(&preds, 0, 3).is_empty());
+        assert!(
+            !eventos
+                .iter()
+                .any(|e| matches!(e, Evento::DestinoFijado { .. }))
+        );
+    }
+
+    // `k` diamantes seguidos: 2^k caminos mínimos del nodo 0 al último
+    fn diamantes(k: usize) -> Vec<Vec<usize>> {
+        let mut preds = vec![vec![]];
+        for i in 0..k {
+            let base = 3 * i;
+            preds.push(vec![base]);
+            preds.push(vec![base]);
+            preds.push(vec![base + 1, base + 2]);
+        }
+        preds
+    }
+
+    #[test]
+    fn caminos_empatados_sin_generarlos_todos() {
+        let preds = diamantes(64);
+        let destino = preds.len() - 1;
+        assert_eq!(contar_caminos(&preds, 0, destino), Some(1 << 64));
+        assert_eq!(contar_caminos(&diamantes(128), 0, 384), None);
+        let primeros = reconstruir_caminos_limitado(&preds, 0, destino, 5);
+        assert_eq!(primeros.len(), 5);
+        assert!(primeros.iter().all(|c| c.len() == 129 && c[0] == 0));
+        let mut iter = iter_caminos(&preds, 0, destino);
+        assert_eq!(iter.next(), Some(primeros[0].clone()));
+    }
+
+    #[test]
+    fn iter_caminos_da_los_mismos_que_reconstruir() {
+        let preds = diamantes(3);
+        let todos = reconstruir_todos_caminos(&preds, 0, 9);
+        assert_eq!(todos.len(), 8);
+        assert_eq!(iter_caminos(&preds, 0, 9).collect::<Vec<_>>(), todos);
+        let mut unicos = todos.clone();
+        unicos.sort();
+        unicos.dedup();
+        assert_eq!(unicos.len(), 8);
+    }
+
+    #[test]
+    fn camino_muy_largo_sin_recursion() {
+        let n = 200_000;
+        let preds: Vec<Vec<usize>> = (0..n)
+            .map(|v| if v == 0 { vec![] } else { vec![v - 1] })
+            .collect();
+        let caminos: Vec<_> = iter_caminos(&preds, 0, n - 1).collect();
+        assert_eq!(caminos.len(), 1);
+        assert_eq!(caminos[0].len(), n);
+    }
+
+    const DEPOSITOS: &str = "nodos: S1 S2 A B C X\nS1 A 1\nA B 1\nS2 B 1\nB C 5";
+
+    #[test]
+    fn multiorigen_reparte_los_nodos_entre_origenes() {
+        let g = de_texto::<i64>(DEPOSITOS);
+        let (dist, preds, fuente, _) = dijkstra_multiorigen(&g, &[(0, 0), (1, 0)]).unwrap();
+        assert_eq!(
+            dist,
+            vec![Some(0), Some(0), Some(1), Some(1), Some(6), None]
+        );
+        assert_eq!(
+            fuente,
+            vec![Some(0), Some(1), Some(0), Some(1), Some(1), None]
+        );
+        assert_eq!(reconstruir_todos_caminos(&preds, 1, 4), vec![vec![1, 3, 4]]);
+
+        // con desfase S2 queda más lejos y B pasa a S1
+        let (dist, _, fuente, _) = dijkstra_multiorigen(&g, &[(0, 0), (1, 5)]).unwrap();
+        assert_eq!((dist[3], fuente[3]), (Some(2), Some(0)));
+    }
+
+    #[test]
+    fn multiorigen_en_empate_gana_el_primero_de_la_lista() {
+        let g = de_texto::<i64>("S1 A 1\nS2 A 1");
+        let (_, preds, fuente, _) = dijkstra_multiorigen(&g, &[(2, 0), (0, 0)]).unwrap();
+        assert_eq!(fuente[1], Some(2));
+        assert_eq!(preds[1].len(), 2);
+        // un origen repetido se queda con su menor desfase
+        let (dist, _, _, _) = dijkstra_multiorigen(&g, &[(0, 3), (0, 1)]).unwrap();
+        assert_eq!(dist[0], Some(1));
+    }
+
+    #[test]
+    fn opciones_bloquean_nodos_y_aristas() {
+        let g = de_texto::<i64>("nodos: A B C D\nA B 1\nB D 1\nA C 2\nC D 2\nA D 1\nA D 9");
+        let mut op = OpcionesConsulta::default();
+        let (dist, _, _) = dijkstra_detallado_con(&g, 0, &op).unwrap();
+        assert_eq!(dist[3], Some(1));
+
+        // bloquear A -> D bloquea también la paralela
+        op.alternar_arista(0, 3);
+        let (dist, _, _) = dijkstra_detallado_con(&g, 0, &op).unwrap();
+        assert_eq!(dist[3], Some(2));
+
+        op.alternar_nodo(1);
+        let (dist, preds, _) = dijkstra_detallado_con(&g, 0, &op).unwrap();
+        assert_eq!(dist, vec![Some(0), None, Some(2), Some(4)]);
+        assert_eq!(reconstruir_todos_caminos(&preds, 0, 3), vec![vec![0, 2, 3]]);
+
+        op.alternar_nodo(2);
+        let (dist, _, _) = dijkstra_detallado_con(&g, 0, &op).unwrap();
+        assert_eq!(dist[3], None);
+
+        // alternar otra vez libera
+        op.alternar_nodo(1);
+        op.alternar_nodo(2);
+        op.alternar_arista(0, 3);
+        assert_eq!(op, OpcionesConsulta::default());
+    }
+
+    #[test]
+    fn opciones_con_origen_bloqueado_es_error() {
+        let g = de_texto::<i64>("A B 1");
+        let mut op = OpcionesConsulta::default();
+        op.alternar_nodo(0);
+        assert!(dijkstra_detallado_con(&g, 0, &op).is_err());
+    }
+
+    // T sale de la cola antes que A (con empates sale primero el índice mayor), así el empate por
+    // A -> T con peso 0 llega cuando T ya está fijo
+    const CERO: &str = "O A 1\nO B 1\nA T 0\nB T 0";
+
+    #[test]
+    fn hasta_guarda_empates_por_aristas_de_peso_cero() {
+        let g = de_texto::<i64>(CERO);
+        let (dist, preds, eventos) = dijkstra_eventos_hasta(&g, 0, 3).unwrap();
+        assert_eq!(dist[3], Some(1));
+        assert_eq!(contar_caminos(&preds, 0, 3), Some(2));
+        assert!(
+            eventos
+                .iter()
+                .any(|e| matches!(e, Evento::DestinoFijado { nodo: 3, .. }))
+        );
+    }
+
+    #[test]
+    fn corrida_completa_guarda_empates_por_aristas_de_peso_cero() {
+        let g = de_texto::<i64>(CERO);
+        let (_, preds, _) = dijkstra_detallado(&g, 0).unwrap();
+        let mut caminos = reconstruir_todos_caminos(&preds, 0, 3);
+        caminos.sort();
+        assert_eq!(caminos, vec![vec![0, 1, 3], vec![0, 2, 3]]);
+    }
+
+    #[test]
+    fn ciclo_de_peso_cero_no_entra_en_preds() {
+        let g = de_texto::<i64>("O A 1\nA B 0\nB A 0");
+        let (dist, preds, _) = dijkstra_detallado(&g, 0).unwrap();
+        assert_eq!(dist, vec![Some(0), Some(1), Some(1)]);
+        assert_eq!(contar_caminos(&preds, 0, 1), Some(1));
+        assert_eq!(contar_caminos(&preds, 0, 2), Some(1));
+    }
+
+    #[test]
+    fn costo_camino_usa_la_paralela_mas_barata() {
+        let g = de_texto::<i32>("A B 5\nA B 2\nB C 1");
+        assert_eq!(costo_camino(&g, &[0, 1, 2]), Ok(3));
+        assert_eq!(costo_camino(&g, &[0]), Ok(0));
+        assert!(costo_camino(&g, &[0, 2]).is_err());
+    }
+
+    #[test]
+    fn costo_camino_no_se_desborda() {
+        let g = de_texto::<i32>("A B 2000000000\nB C 2000000000");
+        assert!(costo_camino(&g, &[0, 1, 2]).is_err());
     }
-    dfs(preds, destino, origen, &mut actual, &mut res);
-    res
 }
@@ -0,0 +1,268 @@
+use crate::dijkstra::{dijkstra_detallado, reconstruir_caminos_limitado};
+use crate::peso::Peso;
+use petgraph::graph::{DiGraph, EdgeIndex, NodeIndex};
+use petgraph::visit::EdgeRef;
+use std::collections::{HashMap, HashSet};
+
+// Par de caminos disjuntos de costo total mínimo (Suurballe), para tener un respaldo si se cae
+// un enlace. Tomar el camino mínimo y después el mejor que no lo toque no sirve: a veces el
+// mínimo bloquea la única forma de tener dos. Suurballe corre Dijkstra, repesa las aristas con
+// las distancias (costos reducidos, ninguno negativo y las del camino mínimo en cero), da vuelta
+// las del camino mínimo y corre Dijkstra otra vez: donde el segundo camino recorre una arista al
+// revés se cancela con el primero y lo que queda son los dos caminos.
+// Para que no compartan nodos cada nodo intermedio se parte en entrada -> salida con una arista de
+// costo cero, así pasar por él usa esa arista y dos caminos ya no pueden.
+
+/// Dos caminos disjuntos con su costo, el más barato primero.
+pub type ParDisjunto<W> = [(Vec<usize>, W); 2];
+
+/// Par de caminos de `origen` a `destino` sin aristas en común (con `sin_nodos_comunes`, tampoco
+/// nodos salvo el origen y el destino) cuya suma de costos es la mínima.
+///
+/// Devuelve `(par, log)`: `par` son los dos `(camino, costo)` de menor a mayor costo, `None` si no
+/// hay dos caminos disjuntos, y `log` la traza de las dos pasadas de
+/// [`dijkstra_detallado`]. Con aristas paralelas los dos caminos pueden ir por el mismo par de
+/// nodos usando aristas distintas. Devuelve `Err` si el origen y el destino son el mismo nodo, con
+/// pesos negativos o si un costo se desborda.
+pub fn caminos_disjuntos<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+    destino: usize,
+    sin_nodos_comunes: bool,
+) -> Result<(Option<ParDisjunto<W>>, Vec<String>), String> {
+    if origen == destino {
+        return Err("El origen y el destino son el mismo nodo".into());
+    }
+    let n = g.node_count();
+    let h = if sin_nodos_comunes {
+        partir_nodos(g, origen, destino)
+    } else {
+        g.clone()
+    };
+
+    // primera pasada: el camino mínimo, con las aristas concretas que usa
+    let (dist, preds, log1) = dijkstra_detallado(&h, origen)?;
+    let mut log = vec!["Primera pasada, camino mínimo:".to_string()];
+    log.extend(log1);
+    if dist[destino].is_none() {
+        return Ok((None, log));
+    }
+    let p1 = reconstruir_caminos_limitado(&preds, origen, destino, 1).remove(0);
+    let aristas_p1: HashSet<usize> = p1
+        .windows(2)
+        .map(|par| mas_barata(&h, par[0], par[1]))
+        .collect();
+
+    // grafo residual con costos reducidos: w + dist[u] - dist[v]; las del camino mínimo al revés
+    // con costo cero. `original[e]` dice de qué arista de `h` viene y si está dada vuelta
+    let mut residual = DiGraph::<String, W>::new();
+    for v in h.node_indices() {
+        residual.add_node(h[v].clone());
+    }
+    let mut original: Vec<(usize, bool)> = vec![];
+    for e in h.edge_references() {
+        let (u, v) = (e.source(), e.target());
+        let id = e.id().index();
+        if aristas_p1.contains(&id) {
+            residual.add_edge(v, u, W::cero());
+            original.push((id, true));
+        } else if let (Some(du), Some(dv)) = (dist[u.index()], dist[v.index()]) {
+            let w = *e.weight();
+            let reducido = w
+                .sumar(du)
+                .and_then(|x| x.restar(dv))
+                .ok_or_else(|| format!("Desborde: costo reducido {} + {} - {}", w, du, dv))?;
+            // con decimales el redondeo puede dejarlo apenas bajo cero
+            residual.add_edge(u, v, reducido.max(W::cero()));
+            original.push((id, false));
+        }
+    }
+
+    // segunda pasada
+    let (dist2, preds2, log2) = dijkstra_detallado(&residual, origen)?;
+    log.push("Segunda pasada, con costos reducidos y el camino mínimo dado vuelta:".into());
+    log.extend(log2);
+    if dist2[destino].is_none() {
+        log.push("No hay un segundo camino disjunto".into());
+        return Ok((None, log));
+    }
+    let p2 = reconstruir_caminos_limitado(&preds2, origen, destino, 1).remove(0);
+
+    // las aristas que quedan: las del primero, menos las que el segundo recorrió al revés, más las
+    // que el segundo recorrió al derecho
+    let mut usadas = aristas_p1;
+    for par in p2.windows(2) {
+        let e = mas_barata(&residual, par[0], par[1]);
+        match original[e] {
+            (id, true) => {
+                usadas.remove(&id);
+                log.push(format!(
+                    "  Se cancela {} -> {} del camino mínimo",
+                    par[1], par[0]
+                ));
+            }
+            (id, false) => {
+                usadas.insert(id);
+            }
+        }
+    }
+
+    let mut salientes: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut ids: Vec<usize> = usadas.into_iter().collect();
+    ids.sort_unstable();
+    for id in ids {
+        let (u, _) = h.edge_endpoints(EdgeIndex::new(id)).unwrap();
+        salientes.entry(u.index()).or_default().push(id);
+    }
+    let mut par = [
+        extraer(&h, &mut salientes, origen, destino, n)?,
+        extraer(&h, &mut salientes, origen, destino, n)?,
+    ];
+    par.sort_by_key(|p| p.1);
+    log.push(format!(
+        "Fin: costos {} y {}, total {}",
+        par[0].1,
+        par[1].1,
+        par[0]
+            .1
+            .sumar(par[1].1)
+            .map_or("desbordado".into(), |t| t.to_string())
+    ));
+    Ok((Some(par), log))
+}
+
+// Cada nodo intermedio v queda como entrada v y salida n + v unidas por una arista de costo cero;
+// el origen y el destino no se parten (los dos caminos salen y llegan ahí)
+fn partir_nodos<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+    destino: usize,
+) -> DiGraph<String, W> {
+    let n = g.node_count();
+    let salida = |v: usize| {
+        if v == origen || v == destino {
+            v
+        } else {
+            n + v
+        }
+    };
+    let mut h = DiGraph::<String, W>::new();
+    for v in g.node_indices() {
+        h.add_node(g[v].clone());
+    }
+    for v in g.node_indices() {
+        h.add_node(format!("{}'", g[v]));
+    }
+    for v in 0..n {
+        if salida(v) != v {
+            h.add_edge(NodeIndex::new(v), NodeIndex::new(salida(v)), W::cero());
+        }
+    }
+    for e in g.edge_references() {
+        let (u, v) = (e.source().index(), e.target().index());
+        h.add_edge(NodeIndex::new(salida(u)), NodeIndex::new(v), *e.weight());
+    }
+    h
+}
+
+// La arista u -> v más barata (hay una, salió de un camino de Dijkstra)
+fn mas_barata<W: Peso>(g: &DiGraph<String, W>, u: usize, v: usize) -> usize {
+    g.edges_connecting(NodeIndex::new(u), NodeIndex::new(v))
+        .min_by_key(|e| *e.weight())
+        .map(|e| e.id().index())
+        .unwrap()
+}
+
+// Saca un camino de origen a destino siguiendo las aristas que quedan. Si vuelve a un nodo se
+// descarta la vuelta (un ciclo de costo cero, uno más caro no estaría en el óptimo)
+fn extraer<W: Peso>(
+    h: &DiGraph<String, W>,
+    salientes: &mut HashMap<usize, Vec<usize>>,
+    origen: usize,
+    destino: usize,
+    n: usize,
+) -> Result<(Vec<usize>, W), String> {
+    let mut nodos = vec![origen];
+    let mut aristas: Vec<usize> = vec![];
+    let mut u = origen;
+    while u != destino {
+        let id = salientes
+            .get_mut(&u)
+            .and_then(|s| s.pop())
+            .expect("las aristas que quedan forman dos caminos");
+        let v = h.edge_endpoints(EdgeIndex::new(id)).unwrap().1.index();
+        if let Some(i) = nodos.iter().position(|&x| x == v) {
+            nodos.truncate(i + 1);
+            aristas.truncate(i);
+        } else {
+            nodos.push(v);
+            aristas.push(id);
+        }
+        u = v;
+    }
+    let mut costo = W::cero();
+    for &id in &aristas {
+        let w = h[EdgeIndex::new(id)];
+        costo = costo.sumar(w).ok_or_else(|| {
+            format!(
+                "Desborde: costo {} + {} no cabe en el tipo de peso",
+                costo, w
+            )
+        })?;
+    }
+    // las salidas de los nodos partidos (índices >= n) son el mismo nodo que su entrada
+    nodos.retain(|&v| v < n);
+    Ok((nodos, costo))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grafo::de_texto;
+
+    fn ordenados(par: ParDisjunto<i64>) -> Vec<(Vec<usize>, i64)> {
+        let mut v = par.to_vec();
+        v.sort();
+        v
+    }
+
+    #[test]
+    fn el_camino_minimo_no_siempre_sirve() {
+        // el mínimo S A B T (3) deja sin segundo camino; el par óptimo es S A T + S B T
+        let g = de_texto::<i64>("nodos: S A B T\nS A 1\nA B 1\nB T 1\nS B 2\nA T 2");
+        for sin_nodos in [false, true] {
+            let (par, _) = caminos_disjuntos(&g, 0, 3, sin_nodos).unwrap();
+            assert_eq!(
+                ordenados(par.unwrap()),
+                vec![(vec![0, 1, 3], 3), (vec![0, 2, 3], 3)]
+            );
+        }
+    }
+
+    #[test]
+    fn sin_nodos_comunes_no_comparte_el_medio() {
+        let g = de_texto::<i64>(
+            "nodos: S A B M C D T\nS A 1\nS B 1\nA M 1\nB M 1\nM C 1\nM D 1\nC T 1\nD T 1",
+        );
+        let (par, _) = caminos_disjuntos(&g, 0, 6, false).unwrap();
+        let par = par.unwrap();
+        assert_eq!(par[0].1 + par[1].1, 8);
+        assert!(par.iter().all(|(c, _)| c.contains(&3)));
+        let (par, _) = caminos_disjuntos(&g, 0, 6, true).unwrap();
+        assert_eq!(par, None);
+    }
+
+    #[test]
+    fn paralelas_cuentan_como_dos_caminos() {
+        let g = de_texto::<i64>("S T 2\nS T 1");
+        let (par, _) = caminos_disjuntos(&g, 0, 1, false).unwrap();
+        assert_eq!(par, Some([(vec![0, 1], 1), (vec![0, 1], 2)]));
+    }
+
+    #[test]
+    fn sin_par_o_mismo_nodo() {
+        let g = de_texto::<i64>("nodos: S A T\nS A 1\nA T 1");
+        assert_eq!(caminos_disjuntos(&g, 0, 2, false).unwrap().0, None);
+        assert!(caminos_disjuntos(&g, 0, 0, false).is_err());
+    }
+}
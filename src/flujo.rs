@@ -0,0 +1,416 @@
+use crate::dijkstra::Entrada;
+use crate::peso::Peso;
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use std::collections::{BinaryHeap, VecDeque};
+
+// Flujos: cada arista deja pasar hasta su capacidad y el peso pasa a ser el costo por unidad.
+// - Flujo máximo con Dinic: por fases, en cada una se calculan los niveles (distancia en aristas
+//   desde el origen en la red residual) y se empuja por caminos que siempre suben un nivel hasta
+//   que no queda ninguno. Son O(V) fases como mucho.
+// - Flujo máximo de costo mínimo con caminos mínimos sucesivos: se empuja por el camino más barato
+//   de la red residual. Las aristas de vuelta tienen costo negativo, así que cada Dijkstra usa los
+//   costos reducidos con los potenciales (las distancias de la vuelta anterior), que nunca son
+//   negativos, como en Johnson.
+// Al terminar, lo que se alcanza desde el origen en la red residual es un lado del corte mínimo.
+
+/// Resultado de [`flujo_maximo`] y [`flujo_costo_minimo`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Flujo<W> {
+    /// Unidades que salen del origen y llegan al destino.
+    pub valor: W,
+    /// Costo total, la suma de flujo por peso de cada arista. Solo lo calcula
+    /// [`flujo_costo_minimo`].
+    pub costo: Option<W>,
+    /// Flujo de cada arista, por índice de arista.
+    pub por_arista: Vec<W>,
+    /// `true` para los nodos del lado del origen del corte mínimo.
+    pub lado_origen: Vec<bool>,
+    /// Índices de las aristas del corte mínimo: van del lado del origen al otro, están saturadas
+    /// y sus capacidades suman `valor`.
+    pub corte: Vec<usize>,
+    /// Cada camino por el que se empujó flujo.
+    pub log: Vec<String>,
+}
+
+/// Flujo máximo de `origen` a `destino` con Dinic, `capacidades[e]` es la de la arista con índice
+/// `e` (lo que devuelve
+/// [`GrafoManual::generar_con_capacidades`](crate::grafo::GrafoManual::generar_con_capacidades)).
+/// Los pesos no se usan.
+///
+/// Devuelve `Err` si el origen y el destino son el mismo nodo, si hay capacidades negativas, si
+/// falta la de alguna arista o si el flujo se desborda.
+pub fn flujo_maximo<W: Peso>(
+    g: &DiGraph<String, W>,
+    capacidades: &[W],
+    origen: usize,
+    destino: usize,
+) -> Result<Flujo<W>, String> {
+    validar(g, capacidades, origen, destino)?;
+    let n = g.node_count();
+    let mut r = Residual::new(g, capacidades);
+    let mut valor = W::cero();
+    let mut log = vec![];
+    let mut fase = 0;
+
+    loop {
+        let mut nivel = r.niveles(origen);
+        let Some(largo) = nivel[destino] else {
+            break;
+        };
+        fase += 1;
+        log.push(format!(
+            "Fase {}: el destino está a {} aristas en la red residual",
+            fase, largo
+        ));
+        // it[u] es la próxima arista de u por probar, las anteriores ya no llevan a nada
+        let mut it = vec![0; n];
+        'fase: loop {
+            let mut camino: Vec<usize> = vec![];
+            let mut u = origen;
+            while u != destino {
+                let mut avanzo = false;
+                while it[u] < r.ady[u].len() {
+                    let e = r.ady[u][it[u]];
+                    let v = r.hasta[e];
+                    if r.resto[e] > W::cero() && nivel[v] == nivel[u].map(|l| l + 1) {
+                        camino.push(e);
+                        u = v;
+                        avanzo = true;
+                        break;
+                    }
+                    it[u] += 1;
+                }
+                if !avanzo {
+                    if u == origen {
+                        break 'fase;
+                    }
+                    // callejón sin salida: se saca de la fase y se retrocede una arista
+                    nivel[u] = None;
+                    let e = camino.pop().unwrap();
+                    u = r.hasta[e ^ 1];
+                    it[u] += 1;
+                }
+            }
+            let f = r.empujar(&camino)?;
+            valor = valor
+                .sumar(f)
+                .ok_or_else(|| format!("Desborde: flujo {} + {}", valor, f))?;
+            log.push(format!("  Empujo {} por {}", f, r.texto(origen, &camino)));
+        }
+    }
+
+    log.push(format!("Fin: flujo máximo {} en {} fases", valor, fase));
+    Ok(r.terminar(g, origen, valor, None, log))
+}
+
+/// Flujo máximo de `origen` a `destino` con el menor costo total, usando el peso de cada arista
+/// como costo por unidad y `capacidades` como en [`flujo_maximo`].
+///
+/// Devuelve los mismos errores que [`flujo_maximo`] y además si hay costos negativos o si el costo
+/// total se desborda.
+pub fn flujo_costo_minimo<W: Peso>(
+    g: &DiGraph<String, W>,
+    capacidades: &[W],
+    origen: usize,
+    destino: usize,
+) -> Result<Flujo<W>, String> {
+    validar(g, capacidades, origen, destino)?;
+    if let Some(e) = g.edge_references().find(|e| *e.weight() < W::cero()) {
+        return Err(format!(
+            "Costo negativo en la arista {} -> {} (w={}), no se admite",
+            e.source().index(),
+            e.target().index(),
+            e.weight()
+        ));
+    }
+    let n = g.node_count();
+    let mut r = Residual::new(g, capacidades);
+    let costos: Vec<W> = g.edge_weights().copied().collect();
+    let mut potencial = vec![W::cero(); n];
+    let mut valor = W::cero();
+    let mut costo = W::cero();
+    let mut log = vec![];
+    let desborde = || "Desborde: el costo no cabe en el tipo de peso".to_string();
+
+    loop {
+        // Dijkstra con costos reducidos; la arista de vuelta e ^ 1 cuesta -c
+        let mut dist: Vec<Option<W>> = vec![None; n];
+        let mut pred: Vec<Option<usize>> = vec![None; n];
+        let mut visitado = vec![false; n];
+        let mut heap: BinaryHeap<Entrada<W>> = BinaryHeap::new();
+        dist[origen] = Some(W::cero());
+        heap.push(Entrada {
+            dist: W::cero(),
+            node: origen,
+        });
+        while let Some(Entrada { dist: d_u, node: u }) = heap.pop() {
+            if visitado[u] {
+                continue;
+            }
+            visitado[u] = true;
+            for &e in &r.ady[u] {
+                let v = r.hasta[e];
+                if visitado[v] || r.resto[e] <= W::cero() {
+                    continue;
+                }
+                let c = costos[e / 2];
+                let reducido = if e % 2 == 0 {
+                    c.sumar(potencial[u]).and_then(|x| x.restar(potencial[v]))
+                } else {
+                    c.sumar(potencial[v]).and_then(|x| potencial[u].restar(x))
+                }
+                .ok_or_else(desborde)?;
+                // con decimales el redondeo puede dejarlo apenas bajo cero
+                let alt = d_u.sumar(reducido.max(W::cero())).ok_or_else(desborde)?;
+                if dist[v].is_none_or(|d| alt < d) {
+                    dist[v] = Some(alt);
+                    pred[v] = Some(e);
+                    heap.push(Entrada { dist: alt, node: v });
+                }
+            }
+        }
+        if dist[destino].is_none() {
+            break;
+        }
+        for v in 0..n {
+            if let Some(d) = dist[v] {
+                potencial[v] = potencial[v].sumar(d).ok_or_else(desborde)?;
+            }
+        }
+
+        let mut camino = vec![];
+        let mut v = destino;
+        while let Some(e) = pred[v] {
+            camino.push(e);
+            v = r.hasta[e ^ 1];
+        }
+        camino.reverse();
+        let f = r.empujar(&camino)?;
+        // el potencial del origen sigue en cero, así que el del destino es el costo del camino
+        let unidad = potencial[destino];
+        valor = valor
+            .sumar(f)
+            .ok_or_else(|| format!("Desborde: flujo {} + {}", valor, f))?;
+        costo = f
+            .multiplicar(unidad)
+            .and_then(|x| costo.sumar(x))
+            .ok_or_else(desborde)?;
+        log.push(format!(
+            "Empujo {} por {} a {} por unidad",
+            f,
+            r.texto(origen, &camino),
+            unidad
+        ));
+    }
+
+    log.push(format!("Fin: flujo máximo {} con costo {}", valor, costo));
+    Ok(r.terminar(g, origen, valor, Some(costo), log))
+}
+
+fn validar<W: Peso>(
+    g: &DiGraph<String, W>,
+    capacidades: &[W],
+    origen: usize,
+    destino: usize,
+) -> Result<(), String> {
+    if origen == destino {
+        return Err("El origen y el destino son el mismo nodo".into());
+    }
+    if capacidades.len() != g.edge_count() {
+        return Err(format!(
+            "Hay {} aristas pero {} capacidades",
+            g.edge_count(),
+            capacidades.len()
+        ));
+    }
+    for e in g.edge_references() {
+        let c = capacidades[e.id().index()];
+        if c < W::cero() {
+            return Err(format!(
+                "Capacidad negativa en la arista {} -> {} (cap={})",
+                e.source().index(),
+                e.target().index(),
+                c
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Red residual: la arista e del grafo es la 2e (lo que queda de capacidad) y su vuelta la 2e + 1
+// (lo que ya pasa, se puede devolver), así la pareja de cualquiera es e ^ 1
+struct Residual<W> {
+    hasta: Vec<usize>,
+    resto: Vec<W>,
+    ady: Vec<Vec<usize>>,
+}
+
+impl<W: Peso> Residual<W> {
+    fn new(g: &DiGraph<String, W>, capacidades: &[W]) -> Self {
+        let mut r = Residual {
+            hasta: vec![],
+            resto: vec![],
+            ady: vec![vec![]; g.node_count()],
+        };
+        for e in g.edge_references() {
+            let (u, v) = (e.source().index(), e.target().index());
+            r.ady[u].push(r.hasta.len());
+            r.hasta.push(v);
+            r.resto.push(capacidades[e.id().index()]);
+            r.ady[v].push(r.hasta.len());
+            r.hasta.push(u);
+            r.resto.push(W::cero());
+        }
+        r
+    }
+
+    // Distancia en aristas desde el origen usando solo las que tienen capacidad
+    fn niveles(&self, origen: usize) -> Vec<Option<usize>> {
+        let mut nivel = vec![None; self.ady.len()];
+        nivel[origen] = Some(0);
+        let mut cola = VecDeque::from([origen]);
+        while let Some(u) = cola.pop_front() {
+            for &e in &self.ady[u] {
+                let v = self.hasta[e];
+                if self.resto[e] > W::cero() && nivel[v].is_none() {
+                    nivel[v] = nivel[u].map(|l| l + 1);
+                    cola.push_back(v);
+                }
+            }
+        }
+        nivel
+    }
+
+    // Empuja por el camino todo lo que deja pasar su arista más chica y devuelve cuánto fue
+    fn empujar(&mut self, camino: &[usize]) -> Result<W, String> {
+        let f = camino.iter().map(|&e| self.resto[e]).min().unwrap();
+        for &e in camino {
+            self.resto[e] = self.resto[e].restar(f).unwrap();
+            self.resto[e ^ 1] = self.resto[e ^ 1]
+                .sumar(f)
+                .ok_or_else(|| format!("Desborde: flujo {} + {}", self.resto[e ^ 1], f))?;
+        }
+        Ok(f)
+    }
+
+    fn texto(&self, origen: usize, camino: &[usize]) -> String {
+        let mut nodos = vec![origen.to_string()];
+        nodos.extend(camino.iter().map(|&e| self.hasta[e].to_string()));
+        nodos.join(" -> ")
+    }
+
+    fn terminar(
+        &self,
+        g: &DiGraph<String, W>,
+        origen: usize,
+        valor: W,
+        costo: Option<W>,
+        log: Vec<String>,
+    ) -> Flujo<W> {
+        let lado_origen: Vec<bool> = self.niveles(origen).iter().map(Option::is_some).collect();
+        let corte = g
+            .edge_references()
+            .filter(|e| lado_origen[e.source().index()] && !lado_origen[e.target().index()])
+            .map(|e| e.id().index())
+            .collect();
+        Flujo {
+            valor,
+            costo,
+            por_arista: (0..g.edge_count()).map(|e| self.resto[2 * e + 1]).collect(),
+            lado_origen,
+            corte,
+            log,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grafo::GrafoManual;
+
+    fn leer(texto: &str) -> (DiGraph<String, i64>, Vec<i64>) {
+        GrafoManual::new(vec![], texto.into())
+            .generar_con_capacidades()
+            .unwrap()
+    }
+
+    // la red del Cormen, flujo máximo 23
+    const CORMEN: &str = "nodos: s v1 v2 v3 v4 t\ns v1 1 cap=16\ns v2 1 cap=13\nv2 v1 1 cap=4\n\
+                          v1 v3 1 cap=12\nv3 v2 1 cap=9\nv2 v4 1 cap=14\nv4 v3 1 cap=7\n\
+                          v3 t 1 cap=20\nv4 t 1 cap=4";
+
+    #[test]
+    fn corte_minimo_igual_a_flujo_maximo() {
+        let (g, cap) = leer(CORMEN);
+        let f = flujo_maximo(&g, &cap, 0, 5).unwrap();
+        assert_eq!(f.valor, 23);
+        assert_eq!(f.costo, None);
+        let corte: i64 = f.corte.iter().map(|&e| cap[e]).sum();
+        assert_eq!(corte, f.valor);
+        assert!(f.lado_origen[0] && !f.lado_origen[5]);
+        for e in g.edge_references() {
+            let (u, v, id) = (e.source().index(), e.target().index(), e.id().index());
+            assert!(0 <= f.por_arista[id] && f.por_arista[id] <= cap[id]);
+            if f.corte.contains(&id) {
+                assert!(f.lado_origen[u] && !f.lado_origen[v]);
+                assert_eq!(f.por_arista[id], cap[id]);
+            }
+        }
+        // lo que entra a cada nodo intermedio sale
+        for x in 1..5 {
+            let balance: i64 = g
+                .edge_references()
+                .map(|e| {
+                    let flujo = f.por_arista[e.id().index()];
+                    match (e.source().index() == x, e.target().index() == x) {
+                        (true, _) => -flujo,
+                        (_, true) => flujo,
+                        _ => 0,
+                    }
+                })
+                .sum();
+            assert_eq!(balance, 0);
+        }
+    }
+
+    #[test]
+    fn costo_minimo_reparte_por_los_caminos_baratos() {
+        // S A T cuesta 2, S A B T 3 y S B T 4; mandar 2 por S B T costaría 10 en vez de 9
+        let (g, cap) =
+            leer("nodos: S A B T\nS A 1 cap=2\nS B 3 cap=2\nA T 1 cap=1\nB T 1 cap=2\nA B 1 cap=1");
+        let f = flujo_costo_minimo(&g, &cap, 0, 3).unwrap();
+        assert_eq!(f.valor, 3);
+        assert_eq!(f.costo, Some(9));
+        assert_eq!(f.por_arista, vec![2, 1, 1, 2, 1]);
+        assert_eq!(flujo_maximo(&g, &cap, 0, 3).unwrap().valor, 3);
+    }
+
+    #[test]
+    fn paralelas_suman_capacidad() {
+        let (g, cap) = leer("S T 1 cap=2\nS T 5 cap=3");
+        assert_eq!(flujo_maximo(&g, &cap, 0, 1).unwrap().valor, 5);
+        let f = flujo_costo_minimo(&g, &cap, 0, 1).unwrap();
+        assert_eq!((f.valor, f.costo), (5, Some(17)));
+        assert_eq!(f.corte.len(), 2);
+    }
+
+    #[test]
+    fn sin_camino_el_flujo_es_cero() {
+        let (g, cap) = leer("nodos: S A T\nS A 1 cap=5");
+        let f = flujo_maximo(&g, &cap, 0, 2).unwrap();
+        assert_eq!(f.valor, 0);
+        assert!(f.corte.is_empty());
+        assert_eq!(flujo_costo_minimo(&g, &cap, 0, 2).unwrap().costo, Some(0));
+    }
+
+    #[test]
+    fn errores() {
+        let (g, cap) = leer("S T 1 cap=-1");
+        assert!(flujo_maximo(&g, &cap, 0, 1).is_err());
+        let (g, cap) = leer("S T 1");
+        assert!(flujo_maximo(&g, &cap, 0, 0).is_err());
+        assert!(flujo_maximo(&g, &[], 0, 1).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+use crate::dijkstra::Entrada;
+use crate::peso::Peso;
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+// Costos de pasar por un nodo y de doblar (pasar de una arista a la siguiente), como en una red de
+// calles: cruzar una plaza cuesta, doblar a la izquierda cuesta más, y algunos giros están
+// prohibidos. Con giros ya no alcanza con una distancia por nodo (a qué arista se llegó importa),
+// así que Dijkstra corre sobre el grafo de aristas: cada arista es un estado y pasar de u -> v a
+// v -> x cuesta el peso de v -> x más el costo de v y el del giro.
+
+/// Costos de nodo y de giro para [`dijkstra_con_giros`]. Lo que no está cuesta cero.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Giros<W> {
+    /// Costo de pasar por el nodo (no se cobra en el origen ni al llegar al destino).
+    pub costo_nodo: HashMap<usize, W>,
+    /// Costo de ir de `u -> v` a `v -> x`, con clave `(u, v, x)`.
+    pub costo_giro: HashMap<(usize, usize, usize), W>,
+    /// Giros `(u, v, x)` que no se pueden hacer.
+    pub prohibidos: HashSet<(usize, usize, usize)>,
+}
+
+// derive(Default) pediría W: Default
+impl<W> Default for Giros<W> {
+    fn default() -> Self {
+        Self {
+            costo_nodo: HashMap::new(),
+            costo_giro: HashMap::new(),
+            prohibidos: HashSet::new(),
+        }
+    }
+}
+
+/// Resultado de [`dijkstra_con_giros`].
+#[derive(Clone, Debug)]
+pub struct ResultadoGiros<W> {
+    /// Costo mínimo para llegar a cada nodo, `None` si no es alcanzable.
+    pub dist: Vec<Option<W>>,
+    /// Traza del algoritmo sobre el grafo de aristas.
+    pub log: Vec<String>,
+    origen: usize,
+    // extremos de cada arista, la arista por la que se llega mejor a cada nodo y la arista
+    // anterior de cada arista en su mejor camino
+    extremos: Vec<(usize, usize)>,
+    llegada: Vec<Option<usize>>,
+    previa: Vec<Option<usize>>,
+}
+
+impl<W> ResultadoGiros<W> {
+    /// El camino mínimo hasta `destino` como secuencia de nodos, `None` si no es alcanzable.
+    /// Puede repetir nodos, por ejemplo para dar la vuelta a la manzana en vez de un giro
+    /// prohibido.
+    pub fn camino(&self, destino: usize) -> Option<Vec<usize>> {
+        if destino == self.origen {
+            return Some(vec![destino]);
+        }
+        let mut e = self.llegada[destino]?;
+        let mut camino = vec![destino];
+        loop {
+            let (u, _) = self.extremos[e];
+            camino.push(u);
+            match self.previa[e] {
+                Some(p) => e = p,
+                None => break,
+            }
+        }
+        camino.reverse();
+        Some(camino)
+    }
+}
+
+/// Dijkstra con costos de nodo y de giro desde `origen`.
+///
+/// Con aristas paralelas cada una es un estado aparte, pero los giros se indican por nodos. De
+/// los caminos empatados se devuelve uno solo. Devuelve `Err` si hay pesos o costos negativos o
+/// si una distancia se desborda.
+pub fn dijkstra_con_giros<W: Peso>(
+    g: &DiGraph<String, W>,
+    giros: &Giros<W>,
+    origen: usize,
+) -> Result<ResultadoGiros<W>, String> {
+    let negativo = giros
+        .costo_nodo
+        .values()
+        .chain(giros.costo_giro.values())
+        .chain(g.edge_weights())
+        .find(|&&c| c < W::cero());
+    if let Some(c) = negativo {
+        return Err(format!(
+            "Costo negativo ({}), Dijkstra con giros no los admite",
+            c
+        ));
+    }
+
+    let n = g.node_count();
+    let m = g.edge_count();
+    let extremos: Vec<(usize, usize)> = g
+        .edge_references()
+        .map(|e| (e.source().index(), e.target().index()))
+        .collect();
+    let mut dist_arista: Vec<Option<W>> = vec![None; m];
+    let mut previa: Vec<Option<usize>> = vec![None; m];
+    let mut visitada = vec![false; m];
+    let mut heap: BinaryHeap<Entrada<W>> = BinaryHeap::new();
+    let mut log = vec![format!(
+        "Inicializo dist[{}]=0, se parte por cada arista que sale de {}",
+        origen, origen
+    )];
+
+    for e in g.edges(petgraph::prelude::NodeIndex::new(origen)) {
+        let id = e.id().index();
+        let w = *e.weight();
+        if dist_arista[id].is_none_or(|d| w < d) {
+            dist_arista[id] = Some(w);
+            heap.push(Entrada { dist: w, node: id });
+            log.push(format!(
+                "  Trazo ({} -> {}, w={}): dist[{}->{}]={}",
+                origen,
+                e.target().index(),
+                w,
+                origen,
+                e.target().index(),
+                w
+            ));
+        }
+    }
+
+    while let Some(Entrada { dist: d_e, node: e }) = heap.pop() {
+        let (u, v) = extremos[e];
+        if visitada[e] {
+            log.push(format!(
+                "  Descarto {}->{} con dist={} (ya visitada)",
+                u, v, d_e
+            ));
+            continue;
+        }
+        visitada[e] = true;
+        log.push(format!("Selecciono {}->{} con dist={}", u, v, d_e));
+
+        let costo_v = giros.costo_nodo.get(&v).copied().unwrap_or(W::cero());
+        for f in g.edges(petgraph::prelude::NodeIndex::new(v)) {
+            let x = f.target().index();
+            let id = f.id().index();
+            if visitada[id] {
+                continue;
+            }
+            if giros.prohibidos.contains(&(u, v, x)) {
+                log.push(format!("  Giro prohibido {} -> {} -> {}", u, v, x));
+                continue;
+            }
+            let giro = giros
+                .costo_giro
+                .get(&(u, v, x))
+                .copied()
+                .unwrap_or(W::cero());
+            let w = *f.weight();
+            let alt = d_e
+                .sumar(costo_v)
+                .and_then(|d| d.sumar(giro))
+                .and_then(|d| d.sumar(w))
+                .ok_or_else(|| {
+                    format!(
+                        "Desborde: dist[{}->{}]={} + nodo {} + giro {} + w={} no cabe en el tipo de peso",
+                        u, v, d_e, costo_v, giro, w
+                    )
+                })?;
+            if dist_arista[id].is_none_or(|d| alt < d) {
+                dist_arista[id] = Some(alt);
+                previa[id] = Some(e);
+                heap.push(Entrada {
+                    dist: alt,
+                    node: id,
+                });
+                log.push(format!(
+                    "  Giro {} -> {} -> {} (w={}, nodo +{}, giro +{}): dist[{}->{}]={}",
+                    u, v, x, w, costo_v, giro, v, x, alt
+                ));
+            }
+        }
+    }
+
+    // cada nodo se queda con la mejor arista que llega a él
+    let mut dist: Vec<Option<W>> = vec![None; n];
+    let mut llegada: Vec<Option<usize>> = vec![None; n];
+    dist[origen] = Some(W::cero());
+    for (e, d) in dist_arista.iter().enumerate() {
+        let v = extremos[e].1;
+        if let Some(d) = *d
+            && v != origen
+            && dist[v].is_none_or(|actual| d < actual)
+        {
+            dist[v] = Some(d);
+            llegada[v] = Some(e);
+        }
+    }
+    log.push(format!(
+        "Fin: {} nodos alcanzados",
+        dist.iter().filter(|d| d.is_some()).count()
+    ));
+
+    Ok(ResultadoGiros {
+        dist,
+        log,
+        origen,
+        extremos,
+        llegada,
+        previa,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grafo::GrafoManual;
+
+    fn correr(texto: &str) -> ResultadoGiros<i64> {
+        let (g, giros) = GrafoManual::new(vec![], texto.into())
+            .generar_con_giros::<i64>()
+            .unwrap();
+        dijkstra_con_giros(&g, &giros, 0).unwrap()
+    }
+
+    // A -> C directo por B (2) o por E (6)
+    const CRUCE: &str = "nodos: A B C E\nA B 1\nB C 1\nA E 3\nE C 3";
+
+    #[test]
+    fn sin_costos_es_dijkstra() {
+        let r = correr(CRUCE);
+        assert_eq!(r.dist, vec![Some(0), Some(1), Some(2), Some(3)]);
+        assert_eq!(r.camino(2), Some(vec![0, 1, 2]));
+        assert_eq!(r.camino(0), Some(vec![0]));
+    }
+
+    #[test]
+    fn costo_de_nodo_y_de_giro() {
+        // los costos del origen y del destino no se cobran
+        let r = correr(&format!("{}\ncosto: A 100\ncosto: C 100", CRUCE));
+        assert_eq!(r.dist[2], Some(2));
+        let r = correr(&format!("{}\ncosto: B 5", CRUCE));
+        assert_eq!(r.dist[2], Some(6));
+        assert_eq!(r.camino(2), Some(vec![0, 3, 2]));
+        let r = correr(&format!("{}\ngiro: A B C 3", CRUCE));
+        assert_eq!(r.dist[2], Some(5));
+        assert_eq!(r.camino(2), Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn giro_prohibido_da_la_vuelta() {
+        let r = correr("nodos: A B C D X\nA B 1\nB C 1\nB D 1\nD B 1\nprohibido: A B C");
+        assert_eq!(r.dist[2], Some(4));
+        assert_eq!(r.camino(2), Some(vec![0, 1, 3, 1, 2]));
+        assert_eq!(r.dist[4], None);
+        assert_eq!(r.camino(4), None);
+    }
+
+    #[test]
+    fn rechaza_costos_negativos() {
+        let (g, giros) = GrafoManual::new(vec![], "A B 1\ncosto: B -1".into())
+            .generar_con_giros::<i64>()
+            .unwrap();
+        assert!(dijkstra_con_giros(&g, &giros, 0).is_err());
+    }
+}
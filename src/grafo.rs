@@ -1,17 +1,32 @@
+use crate::giros::Giros;
+use crate::horario::{FuncionTiempo, Horarios};
+use crate::peso::Peso;
 use petgraph::graph::{DiGraph, NodeIndex};
 use rand::Rng;
+use std::collections::HashMap;
+use std::str::FromStr;
 
 // Funciones para generar digrafos, tanto aleatorios como manuales
 
+/// Genera `n` etiquetas consecutivas estilo hoja de cálculo: A..Z, AA, AB, ..., ZZ, AAA, ...
 pub fn gen_labels(n: usize) -> Vec<String> {
-    (0..n)
-        .map(|i| {
-            let c = (b'A' + (i as u8)) as char;
-            c.to_string()
-        })
-        .collect()
+    (0..n).map(label_de).collect()
 }
 
+// Base 26 "biyectiva", como las columnas de Excel: 0 -> A, 25 -> Z, 26 -> AA
+fn label_de(i: usize) -> String {
+    let mut letras = vec![];
+    let mut k = i + 1;
+    while k > 0 {
+        k -= 1;
+        letras.push((b'A' + (k % 26) as u8) as char);
+        k /= 26;
+    }
+    letras.iter().rev().collect()
+}
+
+/// Generador de digrafos aleatorios: un anillo que asegura conectividad más aristas extra
+/// con probabilidad `p_extra`. Los pesos son enteros entre 1 y 9.
 pub struct GrafoRandom {
     labels: Vec<String>,
     p_extra: f64,
@@ -19,10 +34,12 @@ pub struct GrafoRandom {
 
 // Lo de abajo pero con rng, leer documentación de GrafoManual porfavor, osea los comentarios....
 impl GrafoRandom {
+    /// Crea el generador con un nodo por etiqueta.
     pub fn new(labels: Vec<String>, p_extra: f64) -> Self {
         Self { labels, p_extra }
     }
 
+    /// Genera un digrafo nuevo, cada llamada da un grafo distinto.
     pub fn generar(&self) -> DiGraph<String, i32> {
         let mut grafo = DiGraph::<String, i32>::new();
         let mut idx: Vec<NodeIndex> = Vec::with_capacity(self.labels.len());
@@ -35,6 +52,9 @@ impl GrafoRandom {
         // Asegurar conectividad básica con un anillo
         for i in 0..idx.len() {
             let j = (i + 1) % idx.len();
+            if i == j {
+                continue;
+            }
             let w = rng.random_range(1..=9);
             grafo.add_edge(idx[i], idx[j], w);
         }
@@ -55,21 +75,106 @@ impl GrafoRandom {
     }
 }
 
+/// Coordenadas `(x, y)` de cada nodo, `None` para los que no tienen.
+pub type Coordenadas = Vec<Option<(f64, f64)>>;
+
+/// Digrafo construido a partir de una lista de aristas en texto, una por línea: `U V peso`, o
+/// `U V peso recurso` si la arista lleva además un recurso (tiempo, combustible, peaje...).
+///
+/// Los nodos se crean la primera vez que aparecen, respetando mayúsculas/minúsculas, así que
+/// `Lima Cusco 12` o `warehouse_3 store_7 4` son válidas. Una etiqueta con espacios va entre
+/// comillas dobles: `"San Isidro" Miraflores 3`. También se pueden declarar nodos (por ejemplo
+/// aislados, o para fijar su índice) con una línea `nodos: A B "San Isidro"`, y darles
+/// coordenadas (para las heurísticas de A*) con `pos: Lima -12.04 -77.03`.
+///
+/// Después del peso (y del recurso si lo hay) una arista puede llevar su horario como puntos
+/// `salida=duración`: `A B 5 0=5 60=15 120=5` tarda 5 saliendo en 0, 15 saliendo en 60, 5 desde
+/// 120 y se interpola en el medio (ver [`FuncionTiempo`]). El peso sigue siendo el de los
+/// algoritmos sin horario.
+///
+/// Para los flujos una arista puede llevar su capacidad como `cap=N` en cualquier lugar después
+/// del peso: `A B 4 cap=10` cuesta 4 por unidad y deja pasar hasta 10. Sin `cap=` la capacidad es 1.
+///
+/// Para redes tipo calles se puede declarar el costo de pasar por un nodo con `costo: U 3`, el de
+/// doblar de `U -> V` a `V -> W` con `giro: U V W 2` y prohibir ese giro con `prohibido: U V W`
+/// (ver [`Giros`]).
 pub struct GrafoManual {
     labels: Vec<String>,
     raw: String,
 }
 
 impl GrafoManual {
+    /// `labels` son nodos declarados de antemano (pueden ir vacíos), `raw` el texto con las aristas.
+    /// Los nodos nuevos que aparezcan en `raw` se agregan después de los de `labels`.
     pub fn new(labels: Vec<String>, raw: String) -> Self {
         Self { labels, raw }
     }
 
-    pub fn generar(&self) -> Result<DiGraph<String, i32>, String> {
-        let mut grafo = DiGraph::<String, i32>::new();
-        let mut idx = Vec::with_capacity(self.labels.len());
+    /// Construye el digrafo, el peso de cada nodo es su etiqueta y el de cada arista se parsea
+    /// como `W` (por ejemplo `i32`, `u64` o [`Real`](crate::peso::Real) para decimales).
+    /// Devuelve un mensaje con el número de línea si alguna línea es inválida.
+    pub fn generar<W: Peso + FromStr>(&self) -> Result<DiGraph<String, W>, String> {
+        self.generar_con_coordenadas().map(|(g, _)| g)
+    }
+
+    /// Igual que [`generar`](Self::generar) pero devuelve también las coordenadas declaradas con
+    /// `pos:`, una por nodo (`None` si el nodo no tiene).
+    pub fn generar_con_coordenadas<W: Peso + FromStr>(
+        &self,
+    ) -> Result<(DiGraph<String, W>, Coordenadas), String> {
+        self.generar_todo::<W, W>().map(|l| (l.grafo, l.coords))
+    }
+
+    /// Igual que [`generar`](Self::generar) pero devuelve también el recurso de cada arista
+    /// (cuarta columna, parseada como `R`), indexado por el índice de la arista. Las aristas sin
+    /// cuarta columna tienen recurso cero.
+    pub fn generar_con_recursos<W: Peso + FromStr, R: Peso + FromStr>(
+        &self,
+    ) -> Result<(DiGraph<String, W>, Vec<R>), String> {
+        self.generar_todo::<W, R>().map(|l| (l.grafo, l.recursos))
+    }
+
+    /// Igual que [`generar`](Self::generar) pero devuelve también el horario de cada arista
+    /// (puntos `salida=duración`), indexado por el índice de la arista; `None` en las que no
+    /// tienen.
+    pub fn generar_con_horarios<W: Peso + FromStr>(
+        &self,
+    ) -> Result<(DiGraph<String, W>, Horarios), String> {
+        self.generar_todo::<W, W>().map(|l| (l.grafo, l.horarios))
+    }
+
+    /// Igual que [`generar`](Self::generar) pero devuelve también la capacidad de cada arista
+    /// (`cap=N`, 1 si no tiene), indexada por el índice de la arista.
+    pub fn generar_con_capacidades<W: Peso + FromStr>(
+        &self,
+    ) -> Result<(DiGraph<String, W>, Vec<W>), String> {
+        self.generar_todo::<W, W>()
+            .map(|l| (l.grafo, l.capacidades))
+    }
+
+    /// Igual que [`generar`](Self::generar) pero devuelve también los costos de nodo y de giro y
+    /// los giros prohibidos declarados con `costo:`, `giro:` y `prohibido:`.
+    pub fn generar_con_giros<W: Peso + FromStr>(
+        &self,
+    ) -> Result<(DiGraph<String, W>, Giros<W>), String> {
+        self.generar_todo::<W, W>().map(|l| (l.grafo, l.giros))
+    }
+
+    /// Todo lo que se puede leer del texto de una vez: el grafo y los datos de cada
+    /// `generar_con_*` (recursos como `R`, el resto como `W`). Conviene cuando se necesita más de
+    /// una cosa, así el texto se parsea una sola vez.
+    pub fn generar_todo<W: Peso + FromStr, R: Peso + FromStr>(
+        &self,
+    ) -> Result<GrafoLeido<W, R>, String> {
+        let mut recursos: Vec<R> = vec![];
+        let mut horarios: Horarios = vec![];
+        let mut capacidades: Vec<W> = vec![];
+        let mut grafo = DiGraph::<String, W>::new();
+        let mut idx: HashMap<String, NodeIndex> = HashMap::new();
+        let mut coords: Coordenadas = vec![];
+        let mut giros: Giros<W> = Giros::default();
         for l in &self.labels {
-            idx.push(grafo.add_node(l.clone()));
+            nodo(&mut grafo, &mut idx, l);
         }
         // que demonios es u? v? w?
         // Son los nodos y el peso de la arista :P ejemplo: A B 5 es una arista de A a B con peso 5
@@ -78,39 +183,313 @@ impl GrafoManual {
             if line.is_empty() {
                 continue;
             }
-            let parts: Vec<_> = line.split_whitespace().collect();
-            if parts.len() != 3 {
+            if let Some(decl) = declaracion(line, "nodos:") {
+                let decl = tokens(decl).map_err(|e| format!("Línea {}: {}", lineno + 1, e))?;
+                for l in &decl {
+                    nodo(&mut grafo, &mut idx, l);
+                }
+                continue;
+            }
+            if let Some(decl) = declaracion(line, "pos:") {
+                let decl = tokens(decl).map_err(|e| format!("Línea {}: {}", lineno + 1, e))?;
+                if decl.len() != 3 {
+                    return Err(format!(
+                        "Línea {}: formato inválido. Usa: pos: U x y",
+                        lineno + 1
+                    ));
+                }
+                let xy = (decl[1].parse::<f64>(), decl[2].parse::<f64>());
+                let (Ok(x), Ok(y)) = xy else {
+                    return Err(format!("Línea {}: coordenada inválida", lineno + 1));
+                };
+                let u = nodo(&mut grafo, &mut idx, &decl[0]).index();
+                if coords.len() <= u {
+                    coords.resize(u + 1, None);
+                }
+                coords[u] = Some((x, y));
+                continue;
+            }
+            if let Some(decl) = declaracion(line, "costo:") {
+                let decl = tokens(decl).map_err(|e| format!("Línea {}: {}", lineno + 1, e))?;
+                if decl.len() != 2 {
+                    return Err(format!(
+                        "Línea {}: formato inválido. Usa: costo: U c",
+                        lineno + 1
+                    ));
+                }
+                let c: W = decl[1]
+                    .parse()
+                    .map_err(|_| format!("Línea {}: costo inválido", lineno + 1))?;
+                let u = nodo(&mut grafo, &mut idx, &decl[0]).index();
+                giros.costo_nodo.insert(u, c);
+                continue;
+            }
+            if let Some(decl) = declaracion(line, "giro:") {
+                let decl = tokens(decl).map_err(|e| format!("Línea {}: {}", lineno + 1, e))?;
+                if decl.len() != 4 {
+                    return Err(format!(
+                        "Línea {}: formato inválido. Usa: giro: U V W c",
+                        lineno + 1
+                    ));
+                }
+                let c: W = decl[3]
+                    .parse()
+                    .map_err(|_| format!("Línea {}: costo inválido", lineno + 1))?;
+                let u = nodo(&mut grafo, &mut idx, &decl[0]).index();
+                let v = nodo(&mut grafo, &mut idx, &decl[1]).index();
+                let w = nodo(&mut grafo, &mut idx, &decl[2]).index();
+                giros.costo_giro.insert((u, v, w), c);
+                continue;
+            }
+            if let Some(decl) = declaracion(line, "prohibido:") {
+                let decl = tokens(decl).map_err(|e| format!("Línea {}: {}", lineno + 1, e))?;
+                if decl.len() != 3 {
+                    return Err(format!(
+                        "Línea {}: formato inválido. Usa: prohibido: U V W",
+                        lineno + 1
+                    ));
+                }
+                let u = nodo(&mut grafo, &mut idx, &decl[0]).index();
+                let v = nodo(&mut grafo, &mut idx, &decl[1]).index();
+                let w = nodo(&mut grafo, &mut idx, &decl[2]).index();
+                giros.prohibidos.insert((u, v, w));
+                continue;
+            }
+
+            let mut parts = tokens(line).map_err(|e| format!("Línea {}: {}", lineno + 1, e))?;
+
+            // la capacidad puede ir en cualquier lugar después de las etiquetas
+            let capacidad: W = match parts.iter().skip(2).position(|t| t.starts_with("cap=")) {
+                Some(i) => {
+                    let t = parts.remove(i + 2);
+                    t["cap=".len()..]
+                        .parse()
+                        .map_err(|_| format!("Línea {}: capacidad inválida '{}'", lineno + 1, t))?
+                }
+                None => W::desde_f64(1.0),
+            };
+
+            // los puntos del horario van al final, después de las etiquetas
+            let corte = parts
+                .iter()
+                .skip(2)
+                .position(|t| t.contains('='))
+                .map_or(parts.len(), |i| i + 2);
+            let puntos = parts.split_off(corte);
+            if parts.len() != 3 && parts.len() != 4 {
                 return Err(format!(
-                    "Línea {}: formato inválido. Usa: U V peso",
+                    "Línea {}: formato inválido. Usa: U V peso [recurso] [cap=N] [salida=duración ...]",
                     lineno + 1
                 ));
             }
-            let u_lbl = parts[0].to_uppercase();
-            let v_lbl = parts[1].to_uppercase();
-            let w: i32 = parts[2]
+            let horario = if puntos.is_empty() {
+                None
+            } else {
+                let mut pares = vec![];
+                for p in &puntos {
+                    let par = p
+                        .split_once('=')
+                        .and_then(|(t, d)| Some((t.parse::<f64>().ok()?, d.parse::<f64>().ok()?)));
+                    let Some(par) = par else {
+                        return Err(format!(
+                            "Línea {}: punto de horario inválido '{}', usa salida=duración",
+                            lineno + 1,
+                            p
+                        ));
+                    };
+                    pares.push(par);
+                }
+                Some(
+                    FuncionTiempo::new(pares)
+                        .map_err(|e| format!("Línea {}: {}", lineno + 1, e))?,
+                )
+            };
+            let w: W = parts[2]
                 .parse()
                 .map_err(|_| format!("Línea {}: peso inválido", lineno + 1))?;
+            let r: R = match parts.get(3) {
+                Some(t) => t
+                    .parse()
+                    .map_err(|_| format!("Línea {}: recurso inválido", lineno + 1))?,
+                None => R::cero(),
+            };
 
-            let u = self
-                .labels
-                .iter()
-                .position(|s| s == &u_lbl)
-                .ok_or_else(|| format!("Línea {}: nodo '{}' no existe", lineno + 1, u_lbl))?;
-            let v = self
-                .labels
-                .iter()
-                .position(|s| s == &v_lbl)
-                .ok_or_else(|| format!("Línea {}: nodo '{}' no existe", lineno + 1, v_lbl))?;
+            let u = nodo(&mut grafo, &mut idx, &parts[0]);
+            let v = nodo(&mut grafo, &mut idx, &parts[1]);
 
             if u == v {
                 continue;
             }
-            grafo.add_edge(
-                petgraph::prelude::NodeIndex::new(u),
-                petgraph::prelude::NodeIndex::new(v),
-                w.max(1),
-            );
+            // los pesos se guardan tal cual, dijkstra rechaza los negativos y bellman-ford los acepta
+            grafo.add_edge(u, v, w);
+            recursos.push(r);
+            horarios.push(horario);
+            capacidades.push(capacidad);
+        }
+        coords.resize(grafo.node_count(), None);
+        Ok(GrafoLeido {
+            grafo,
+            coords,
+            recursos,
+            horarios,
+            capacidades,
+            giros,
+        })
+    }
+}
+
+// Para las pruebas: el grafo de un texto con el formato del modo manual
+#[cfg(test)]
+pub(crate) fn de_texto<W: Peso + FromStr>(texto: &str) -> DiGraph<String, W> {
+    GrafoManual::new(vec![], texto.into()).generar().unwrap()
+}
+
+/// Lo que devuelve [`GrafoManual::generar_todo`]. Todo lo que va por arista está indexado por el
+/// índice de la arista en `grafo`.
+#[derive(Clone, Debug)]
+pub struct GrafoLeido<W, R> {
+    pub grafo: DiGraph<String, W>,
+    /// Las coordenadas de `pos:`, una por nodo.
+    pub coords: Coordenadas,
+    /// La cuarta columna de cada arista, cero si no tiene.
+    pub recursos: Vec<R>,
+    /// Los puntos `salida=duración` de cada arista, `None` si no tiene.
+    pub horarios: Horarios,
+    /// El `cap=N` de cada arista, 1 si no tiene.
+    pub capacidades: Vec<W>,
+    /// Los `costo:`, `giro:` y `prohibido:`.
+    pub giros: Giros<W>,
+}
+
+// Lo que sigue a la palabra clave si la línea es esa declaración: la palabra tiene que ir sola
+// (`pos: A 1 2`), así una etiqueta como `pos:A` no se toma como declaración
+fn declaracion<'a>(line: &'a str, clave: &str) -> Option<&'a str> {
+    let resto = line.strip_prefix(clave)?;
+    (resto.is_empty() || resto.starts_with(char::is_whitespace)).then_some(resto)
+}
+
+// Devuelve el nodo con esa etiqueta, creándolo si es la primera vez que aparece
+fn nodo<W>(
+    grafo: &mut DiGraph<String, W>,
+    idx: &mut HashMap<String, NodeIndex>,
+    label: &str,
+) -> NodeIndex {
+    if let Some(&i) = idx.get(label) {
+        return i;
+    }
+    let i = grafo.add_node(label.to_string());
+    idx.insert(label.to_string(), i);
+    i
+}
+
+// Separa una línea por espacios, lo que va entre comillas dobles es un solo token (sin las comillas)
+fn tokens(line: &str) -> Result<Vec<String>, String> {
+    let mut out = vec![];
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut t = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some(c) => t.push(c),
+                    None => return Err("comillas sin cerrar".into()),
+                }
+            }
+            if t.is_empty() {
+                return Err("etiqueta vacía".into());
+            }
+            out.push(t);
+        } else {
+            let mut t = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                t.push(c);
+                chars.next();
+            }
+            out.push(t);
         }
-        Ok(grafo)
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn etiquetas_siguen_despues_de_la_z() {
+        let l = gen_labels(703);
+        assert_eq!(
+            (&l[0][..], &l[25][..], &l[26][..], &l[27][..]),
+            ("A", "Z", "AA", "AB")
+        );
+        assert_eq!((&l[701][..], &l[702][..]), ("ZZ", "AAA"));
+    }
+
+    #[test]
+    fn grafo_aleatorio_sin_limite_de_nodos() {
+        let g = GrafoRandom::new(gen_labels(40), 0.0).generar();
+        assert_eq!(g.node_count(), 40);
+        // solo el anillo
+        assert_eq!(g.edge_count(), 40);
+    }
+
+    #[test]
+    fn etiquetas_libres_y_entre_comillas() {
+        let g = de_texto::<i64>("nodos: Callao\nLima \"San Isidro\" 3\nwarehouse_3 lima 1");
+        let labels: Vec<&str> = g.node_weights().map(String::as_str).collect();
+        assert_eq!(
+            labels,
+            vec!["Callao", "Lima", "San Isidro", "warehouse_3", "lima"]
+        );
+        assert_eq!(g.edge_count(), 2);
+    }
+
+    #[test]
+    fn lineas_invalidas_dicen_el_numero_de_linea() {
+        let m = GrafoManual::new(vec![], "A B 1\nA B x".into());
+        assert_eq!(m.generar::<i64>().unwrap_err(), "Línea 2: peso inválido");
+        let m = GrafoManual::new(vec![], "A B".into());
+        assert!(m.generar::<i64>().unwrap_err().starts_with("Línea 1:"));
+    }
+
+    #[test]
+    fn generar_todo_lee_cada_dato_por_arista() {
+        let texto = "nodos: A B C\nA B 4 2 cap=10\nB C 1 0=3 10=5\npos: A 0 1\ncosto: B 7\nprohibido: A B C";
+        let l = GrafoManual::new(vec![], texto.into())
+            .generar_todo::<i64, i64>()
+            .unwrap();
+        assert_eq!(l.grafo.node_count(), 3);
+        assert_eq!(l.grafo.edge_count(), 2);
+        assert_eq!(l.coords, vec![Some((0.0, 1.0)), None, None]);
+        assert_eq!(l.recursos, vec![2, 0]);
+        assert_eq!(l.capacidades, vec![10, 1]);
+        assert!(l.horarios[0].is_none());
+        assert_eq!(
+            l.horarios[1].as_ref().unwrap().puntos(),
+            &[(0.0, 3.0), (10.0, 5.0)]
+        );
+        assert_eq!(l.giros.costo_nodo.get(&1), Some(&7));
+        assert!(l.giros.prohibidos.contains(&(0, 1, 2)));
+    }
+
+    #[test]
+    fn etiquetas_que_empiezan_como_palabra_clave_son_aristas() {
+        let g = de_texto::<i64>("pos:1 giro:2 3\nnodos:x costo:y 4");
+        let labels: Vec<&str> = g.node_weights().map(String::as_str).collect();
+        assert_eq!(labels, vec!["pos:1", "giro:2", "nodos:x", "costo:y"]);
+        assert_eq!(g.edge_count(), 2);
+    }
+
+    #[test]
+    fn aristas_paralelas_se_guardan_todas() {
+        let g = de_texto::<i64>("A B 3\nA B 1");
+        assert_eq!(g.edge_count(), 2);
     }
 }
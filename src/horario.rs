@@ -0,0 +1,269 @@
+use crate::dijkstra::{Entrada, Resultado, es_ancestro};
+use crate::peso::{Peso, Real};
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use std::collections::BinaryHeap;
+
+// Dijkstra con horarios: lo que se tarda en una arista depende de a qué hora se sale (tráfico,
+// frecuencia de buses...). En vez de distancias se fija la hora de llegada más temprana a cada
+// nodo. Con funciones FIFO (salir más tarde nunca hace llegar antes) sigue valiendo la idea de
+// Dijkstra: llegar antes a un nodo nunca es peor, así que esperar no sirve y cada nodo se fija
+// una vez.
+
+/// Tiempo de viaje de una arista según la hora de salida, lineal a trozos entre los puntos
+/// `(salida, duración)` y constante antes del primero y después del último.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FuncionTiempo {
+    puntos: Vec<(f64, f64)>,
+}
+
+impl FuncionTiempo {
+    /// Crea la función con los puntos `(salida, duración)`. Devuelve `Err` si no hay puntos, si
+    /// las salidas no van en orden creciente, si alguna duración es negativa o no finita, o si no
+    /// es FIFO (entre dos puntos la duración no puede bajar más rápido de lo que avanza el reloj).
+    pub fn new(puntos: Vec<(f64, f64)>) -> Result<Self, String> {
+        if puntos.is_empty() {
+            return Err("la función de tiempo no tiene puntos".into());
+        }
+        for &(t, d) in &puntos {
+            if !t.is_finite() || !d.is_finite() || d < 0.0 {
+                return Err(format!("punto inválido {}={}", t, d));
+            }
+        }
+        for par in puntos.windows(2) {
+            let ((t1, d1), (t2, d2)) = (par[0], par[1]);
+            if t2 <= t1 {
+                return Err(format!(
+                    "las salidas deben ir en orden: {} y después {}",
+                    t1, t2
+                ));
+            }
+            // saliendo en t2 se llega en t2 + d2, no puede ser antes que t1 + d1
+            if t2 + d2 < t1 + d1 {
+                return Err(format!(
+                    "no es FIFO: saliendo en {} se llega en {}, antes que saliendo en {} ({})",
+                    t2,
+                    t2 + d2,
+                    t1,
+                    t1 + d1
+                ));
+            }
+        }
+        Ok(Self { puntos })
+    }
+
+    /// Función que tarda siempre lo mismo.
+    pub fn constante(duracion: f64) -> Self {
+        Self {
+            puntos: vec![(0.0, duracion)],
+        }
+    }
+
+    /// Los puntos `(salida, duración)` con los que se creó.
+    pub fn puntos(&self) -> &[(f64, f64)] {
+        &self.puntos
+    }
+
+    /// Cuánto se tarda saliendo en `t`.
+    pub fn duracion(&self, t: f64) -> f64 {
+        let p = &self.puntos;
+        if t <= p[0].0 {
+            return p[0].1;
+        }
+        // el primer punto con salida > t, el tramo es el que termina ahí
+        match p.iter().position(|&(ti, _)| ti > t) {
+            None => p[p.len() - 1].1,
+            Some(i) => {
+                let ((t1, d1), (t2, d2)) = (p[i - 1], p[i]);
+                d1 + (d2 - d1) * (t - t1) / (t2 - t1)
+            }
+        }
+    }
+}
+
+/// Horario de cada arista por índice de arista, `None` si tarda siempre su peso (lo que
+/// devuelve [`GrafoManual::generar_con_horarios`](crate::grafo::GrafoManual::generar_con_horarios)).
+pub type Horarios = Vec<Option<FuncionTiempo>>;
+
+/// Dijkstra dependiente del tiempo: saliendo de `origen` en `salida`, la hora más temprana a la
+/// que se llega a cada nodo. Las aristas con `horarios[e]` usan esa función, las demás tardan
+/// siempre su peso.
+///
+/// Devuelve `(llegada, preds, log)` como [`dijkstra_detallado`](crate::dijkstra::dijkstra_detallado):
+/// `llegada[v]` es `None` si no es alcanzable y `preds[v]` los predecesores que empatan en la
+/// llegada más temprana. Devuelve `Err` si `salida` no es un número finito, si falta el horario de
+/// alguna arista o si una arista sin horario tiene peso negativo.
+pub fn dijkstra_con_horario<W: Peso>(
+    g: &DiGraph<String, W>,
+    horarios: &[Option<FuncionTiempo>],
+    origen: usize,
+    salida: f64,
+) -> Result<Resultado<Real>, String> {
+    if !salida.is_finite() {
+        return Err(format!("Hora de salida inválida: {}", salida));
+    }
+    if horarios.len() != g.edge_count() {
+        return Err(format!(
+            "Hay {} aristas pero {} horarios",
+            g.edge_count(),
+            horarios.len()
+        ));
+    }
+    let n = g.node_count();
+    let mut llegada: Vec<Option<Real>> = vec![None; n];
+    let mut preds: Vec<Vec<usize>> = vec![vec![]; n];
+    let mut visitado = vec![false; n];
+    let mut heap: BinaryHeap<Entrada<Real>> = BinaryHeap::new();
+    let mut log = vec![];
+
+    llegada[origen] = Some(Real(salida));
+    heap.push(Entrada {
+        dist: Real(salida),
+        node: origen,
+    });
+    log.push(format!(
+        "Inicializo llegada[{}]={} (hora de salida), resto = ∞",
+        origen, salida
+    ));
+
+    while let Some(Entrada { dist: t_u, node: u }) = heap.pop() {
+        if visitado[u] {
+            log.push(format!(
+                "  Descarto u={} con llegada={} (ya visitado)",
+                u, t_u
+            ));
+            continue;
+        }
+        visitado[u] = true;
+        log.push(format!("Selecciono u={} con llegada={}", u, t_u));
+
+        for e in g.edges(petgraph::prelude::NodeIndex::new(u)) {
+            let v = e.target().index();
+            let d = match &horarios[e.id().index()] {
+                Some(f) => f.duracion(t_u.0),
+                None => {
+                    let w = *e.weight();
+                    if w < W::cero() {
+                        return Err(format!(
+                            "Peso negativo en la arista {} -> {} (w={}) sin horario",
+                            u, v, w
+                        ));
+                    }
+                    w.a_f64()
+                }
+            };
+            let alt = Real(t_u.0 + d);
+            // un nodo fijo solo puede recibir empates por aristas que tardan 0
+            if visitado[v] {
+                if llegada[v] == Some(alt) && !preds[v].contains(&u) && !es_ancestro(&preds, v, u) {
+                    preds[v].push(u);
+                    log.push(format!("  Empate óptimo hacia {}: también via {}", v, u));
+                }
+                continue;
+            }
+            match llegada[v] {
+                None => {
+                    llegada[v] = Some(alt);
+                    preds[v] = vec![u];
+                    heap.push(Entrada { dist: alt, node: v });
+                    log.push(format!(
+                        "  Trazo ({} -> {}, sale {}, tarda {}): llegada[{}]={}",
+                        u, v, t_u, d, v, alt
+                    ));
+                }
+                Some(curr) if alt < curr => {
+                    llegada[v] = Some(alt);
+                    preds[v] = vec![u];
+                    heap.push(Entrada { dist: alt, node: v });
+                    log.push(format!(
+                        "  Mejora ({} -> {}, sale {}, tarda {}): llegada[{}] {}→{}",
+                        u, v, t_u, d, v, curr, alt
+                    ));
+                }
+                Some(curr) if alt == curr && !preds[v].contains(&u) => {
+                    preds[v].push(u);
+                    log.push(format!("  Empate óptimo hacia {}: también via {}", v, u));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    log.push(format!(
+        "Fin: {} nodos alcanzados",
+        llegada.iter().filter(|d| d.is_some()).count()
+    ));
+    Ok((llegada, preds, log))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::contar_caminos;
+    use crate::grafo::{GrafoManual, de_texto};
+
+    #[test]
+    fn interpola_entre_puntos() {
+        let f = FuncionTiempo::new(vec![(0.0, 5.0), (60.0, 15.0), (120.0, 5.0)]).unwrap();
+        let d: Vec<f64> = [-10.0, 30.0, 60.0, 90.0, 200.0]
+            .iter()
+            .map(|&t| f.duracion(t))
+            .collect();
+        assert_eq!(d, vec![5.0, 10.0, 15.0, 10.0, 5.0]);
+    }
+
+    #[test]
+    fn rechaza_funciones_invalidas() {
+        assert!(FuncionTiempo::new(vec![]).is_err());
+        assert!(FuncionTiempo::new(vec![(10.0, 1.0), (5.0, 1.0)]).is_err());
+        assert!(FuncionTiempo::new(vec![(0.0, -1.0)]).is_err());
+        // saliendo en 10 se llegaría en 10, antes que saliendo en 0 (en 100)
+        assert!(FuncionTiempo::new(vec![(0.0, 100.0), (10.0, 0.0)]).is_err());
+    }
+
+    #[test]
+    fn la_hora_de_salida_cambia_el_camino() {
+        // A -> B se congestiona a las 60, conviene dar la vuelta por C; D no es alcanzable
+        let (g, horarios) = GrafoManual::new(
+            vec![],
+            "nodos: A B C D\nA B 5 0=5 60=50\nA C 10\nC B 10".into(),
+        )
+        .generar_con_horarios::<i64>()
+        .unwrap();
+        let (llegada, preds, _) = dijkstra_con_horario(&g, &horarios, 0, 0.0).unwrap();
+        assert_eq!(llegada[1], Some(Real(5.0)));
+        assert_eq!(preds[1], vec![0]);
+        let (llegada, preds, _) = dijkstra_con_horario(&g, &horarios, 0, 60.0).unwrap();
+        assert_eq!(llegada[1], Some(Real(80.0)));
+        assert_eq!(preds[1], vec![2]);
+        assert_eq!(llegada[3], None);
+    }
+
+    #[test]
+    fn empates_por_aristas_que_tardan_cero() {
+        // T se fija antes que A (con empates sale primero el índice mayor), así el empate por
+        // A -> T llega cuando T ya está fijo; A <-> B tardan 0 y no pueden armar un ciclo
+        let g = de_texto::<i64>("O A 1\nO B 1\nA T 0\nB T 0\nA B 0\nB A 0");
+        let horarios = vec![None; g.edge_count()];
+        let (llegada, preds, _) = dijkstra_con_horario(&g, &horarios, 0, 0.0).unwrap();
+        assert_eq!(llegada[3], Some(Real(1.0)));
+        let mut p = preds[3].clone();
+        p.sort();
+        assert_eq!(p, vec![1, 2]);
+        assert_eq!(contar_caminos(&preds, 0, 3), Some(3));
+    }
+
+    #[test]
+    fn salida_no_finita_es_error() {
+        let g = de_texto::<i64>("A B 1");
+        for t in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(dijkstra_con_horario(&g, &[None], 0, t).is_err());
+        }
+    }
+
+    #[test]
+    fn falta_el_horario_de_una_arista() {
+        let g = de_texto::<i64>("A B 1");
+        assert!(dijkstra_con_horario(&g, &[], 0, 0.0).is_err());
+    }
+}
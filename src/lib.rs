@@ -0,0 +1,94 @@
+//! Biblioteca del visualizador de grafos: construcción de digrafos y caminos mínimos.
+//!
+//! Todo lo que no es interfaz vive aquí, así otros proyectos pueden usar el solver sin
+//! arrastrar eframe/egui. La interfaz gráfica está detrás del feature `gui` (activo por defecto),
+//! para usarla sin interfaz:
+//!
+//! ```toml
+//! projecto_computacional = { version = "0.2", default-features = false }
+//! ```
+//!
+//! Flujo típico: construir el grafo con [`GrafoManual`] o [`GrafoRandom`], correr
+//! [`dijkstra_detallado`] desde un origen y reconstruir los caminos con
+//! [`reconstruir_todos_caminos`].
+//!
+//! ```
+//! use projecto_computacional::{GrafoManual, dijkstra_detallado, reconstruir_todos_caminos};
+//!
+//! let g = GrafoManual::new(vec![], "A B 1\nB C 2\nA C 3".into())
+//!     .generar::<i32>()
+//!     .unwrap();
+//! let (dist, preds, _log) = dijkstra_detallado(&g, 0).unwrap();
+//! assert_eq!(dist[2], Some(3));
+//! assert_eq!(
+//!     reconstruir_todos_caminos(&preds, 0, 2),
+//!     vec![vec![0, 2], vec![0, 1, 2]]
+//! );
+//! ```
+//!
+//! Los solvers son genéricos sobre el tipo de peso ([`Peso`]) y devuelven `Err` si una distancia
+//! se desborda en vez de recortarla.
+//!
+//! Módulos:
+//!
+//! - [`grafo`]: leer y generar grafos, con recursos, horarios, giros o capacidades por arista.
+//! - [`peso`]: el trait [`Peso`], implementado para los enteros y para [`Real`] (decimales).
+//! - [`dijkstra`]: [`dijkstra_detallado`] y sus variantes: traza como [`Evento`]s, parar al
+//!   fijar un destino, excluir nodos o aristas con [`OpcionesConsulta`] y varios orígenes.
+//! - [`arbol_caminos`]: [`ArbolCaminos`], el árbol y el DAG de caminos mínimos ya armados.
+//! - [`bellman_ford`]: pesos negativos, devuelve el ciclo negativo si lo hay.
+//! - [`astar`]: A* de un origen a un destino con coordenadas.
+//! - [`bidireccional`]: Dijkstra desde el origen y el destino a la vez.
+//! - [`todos_pares`]: [`floyd_warshall`] (grafos chicos o densos) y [`johnson`] (dispersos).
+//! - [`yen`]: los `k` caminos simples más cortos aunque no empaten.
+//! - [`paradas`]: rutas con paradas obligatorias, en orden o en el que convenga.
+//! - [`presupuesto`]: el camino más barato sin pasar un presupuesto de recurso y el frente de
+//!   Pareto de los dos criterios.
+//! - [`semianillos`]: el camino más ancho (cuello de botella) y el más confiable.
+//! - [`horario`]: llegada más temprana cuando lo que se tarda depende de la hora de salida.
+//! - [`giros`]: costo por nodo y por doblar, y giros prohibidos.
+//! - [`disjuntos`]: el par de caminos sin aristas o nodos en común de menor costo (Suurballe).
+//! - [`flujo`]: flujo máximo (Dinic) y de costo mínimo, con el corte mínimo.
+//! - [`arbol`]: árboles generadores mínimos ([`prim`], [`kruskal`]) y la arborescencia mínima
+//!   desde una raíz (Chu–Liu/Edmonds).
+
+pub mod arbol;
+pub mod arbol_caminos;
+pub mod astar;
+pub mod bellman_ford;
+pub mod bidireccional;
+pub mod dijkstra;
+pub mod disjuntos;
+pub mod flujo;
+pub mod giros;
+pub mod grafo;
+pub mod horario;
+pub mod paradas;
+pub mod peso;
+pub mod presupuesto;
+pub mod semianillos;
+pub mod todos_pares;
+pub mod yen;
+
+pub use arbol::{arborescencia_minima, kruskal, prim};
+pub use arbol_caminos::{ArbolCaminos, arbol_caminos_minimos};
+pub use astar::astar_detallado;
+pub use bellman_ford::{FalloBellmanFord, bellman_ford_detallado};
+pub use bidireccional::{EventoBidireccional, ResultadoBidireccional, dijkstra_bidireccional};
+pub use dijkstra::{
+    Evento, OpcionesConsulta, Resultado, ResultadoMultiorigen, contar_caminos, dijkstra_detallado,
+    dijkstra_detallado_con, dijkstra_detallado_hasta, dijkstra_eventos, dijkstra_eventos_con,
+    dijkstra_eventos_hasta, dijkstra_eventos_multiorigen, dijkstra_multiorigen, iter_caminos,
+    reconstruir_caminos_limitado, reconstruir_todos_caminos,
+};
+pub use disjuntos::{ParDisjunto, caminos_disjuntos};
+pub use flujo::{Flujo, flujo_costo_minimo, flujo_maximo};
+pub use giros::{Giros, ResultadoGiros, dijkstra_con_giros};
+pub use grafo::{Coordenadas, GrafoLeido, GrafoManual, GrafoRandom, gen_labels};
+pub use horario::{FuncionTiempo, Horarios, dijkstra_con_horario};
+pub use paradas::{RutaConParadas, Tramo, ruta_con_paradas};
+pub use peso::{Peso, Real};
+pub use presupuesto::{camino_con_presupuesto, frente_pareto};
+pub use semianillos::{Capacidad, camino_mas_ancho, camino_mas_confiable};
+pub use todos_pares::{camino_siguiente, floyd_warshall, johnson};
+pub use yen::k_caminos_mas_cortos;
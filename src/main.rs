@@ -1,15 +1,31 @@
 //Welcome to hell
 #![cfg_attr(target_os = "windows", windows_subsystem = "windows")]
 mod dibujar;
-mod dijkstra;
-mod grafo;
 use eframe::{App, egui};
-use grafo::{GrafoManual, GrafoRandom, gen_labels};
 use petgraph::graph::DiGraph;
 use petgraph::visit::EdgeRef;
+use projecto_computacional::arbol;
+use projecto_computacional::arbol_caminos::ArbolCaminos;
+use projecto_computacional::astar;
+use projecto_computacional::bellman_ford::{self, FalloBellmanFord};
+use projecto_computacional::bidireccional;
+use projecto_computacional::dijkstra::{self, OpcionesConsulta};
+use projecto_computacional::disjuntos;
+use projecto_computacional::flujo::{self, Flujo};
+use projecto_computacional::giros::{self, Giros};
+use projecto_computacional::grafo::{
+    Coordenadas, GrafoLeido, GrafoManual, GrafoRandom, gen_labels,
+};
+use projecto_computacional::horario::{self, Horarios};
+use projecto_computacional::paradas::{self, Tramo};
+use projecto_computacional::peso::Real;
+use projecto_computacional::presupuesto;
+use projecto_computacional::semianillos;
+use projecto_computacional::todos_pares;
+use projecto_computacional::yen;
 // La interfaz no sabia hacerla asi que tuve que investigar, podra ser mejor pero a las justas entiendo como funciona.
 // El proximo trabajo lo haré en python xd
-// main.rs solo maneja la interfaz y su inicialización, la logica del grafo y dijkstra estan en sus respectivos modulos
+// main.rs solo maneja la interfaz y su inicialización, la logica del grafo y dijkstra estan en la biblioteca (lib.rs)
 // hecho con easygui (egui + eframe), no era tan
 struct DijkstraApp {
     n: usize,
@@ -18,9 +34,42 @@ struct DijkstraApp {
     manual_input: String,
     origen: usize,
     destino: usize,
+    algoritmo: Algoritmo,
+    corrido: Algoritmo, // el que produjo lo que se muestra, el elegido puede haber cambiado después
+    heuristica: Heuristica,
+    escala: f64,
+    parar_en_destino: bool,
     grafo: Option<DiGraph<String, i32>>,
+    coords: Coordenadas, // de las líneas `pos:` del modo manual
+    recursos: Vec<i32>,  // recurso de cada arista (cuarta columna del modo manual), por índice
+    presupuesto: i32,
+    horarios: Horarios,    // horario `salida=duración` de cada arista, por índice
+    salida: f64,           // hora de salida del origen para el modo con horario
+    giros: Giros<i32>, // costos de nodo y de giro del modo manual (`costo:`, `giro:`, `prohibido:`)
+    capacidades: Vec<i32>, // capacidad de cada arista (`cap=N` del modo manual, 1 si falta), por índice
+    flujo: Option<Flujo<i32>>, // último flujo calculado, se dibuja sobre las aristas
+    arbol: Option<Vec<usize>>, // aristas del árbol generador (o de caminos mínimos), por índice
+    atenuar_fuera_del_arbol: bool, // con Dijkstra, atenuar lo que no es del árbol de caminos mínimos
     log: Vec<String>,
     caminos: Vec<Vec<usize>>,
+    k: usize,                // cuántos caminos pide Yen
+    sin_nodos_comunes: bool, // para el par disjunto, que tampoco compartan nodos
+    origenes_texto: String,  // orígenes para varios orígenes: `A B:3 C`
+    origenes: Vec<(usize, i32)>,
+    fuente: Vec<Option<(usize, i32)>>, // origen que atiende a cada nodo y a qué costo
+    bloqueos: OpcionesConsulta,        // nodos/aristas bloqueados con clic en el dibujo
+    paradas_texto: String,             // paradas obligatorias: `C D`
+    paradas_en_orden: bool,
+    tramos: Vec<Tramo<i32>>, // tramos de la última ruta con paradas
+    ruta_exacta: bool,
+    resumen: Option<String>, // valor del camino cuando no es la suma de pesos (recurso, capacidad...)
+    frente: Vec<(Vec<usize>, i32, i32)>, // frente de Pareto (camino, costo, recurso)
+    frente_sel: usize,       // punto del frente elegido con clic
+    max_caminos: usize,      // cuántos caminos empatados se listan como mucho
+    total_caminos: Option<u128>, // cuántos hay en total, None si ni en u128 caben
+    todos_pares: TodosPares,
+    tabla: Option<Tabla>, // distancias entre todos los pares, se borra al reconstruir el grafo
+    ciclo: Vec<usize>,    // ciclo negativo que encontró bellman-ford, si hubo
     error: Option<String>,
     labels: Vec<String>,
 
@@ -38,6 +87,108 @@ enum Modo {
     Manual,
 }
 
+#[derive(Clone, Copy, PartialEq)]
+enum Algoritmo {
+    Dijkstra,
+    BellmanFord,
+    AEstrella,
+    Bidireccional,
+    Yen,
+    VariosOrigenes,
+    Paradas,
+    Presupuesto,
+    Pareto,
+    Ancho,
+    Confiable,
+    Horario,
+    Giros,
+    Disjuntos,
+    FlujoMaximo,
+    FlujoCostoMinimo,
+    Prim,
+    Kruskal,
+    Arborescencia,
+}
+
+impl Algoritmo {
+    const TODOS: [Algoritmo; 19] = [
+        Algoritmo::Dijkstra,
+        Algoritmo::BellmanFord,
+        Algoritmo::AEstrella,
+        Algoritmo::Bidireccional,
+        Algoritmo::Yen,
+        Algoritmo::VariosOrigenes,
+        Algoritmo::Paradas,
+        Algoritmo::Presupuesto,
+        Algoritmo::Pareto,
+        Algoritmo::Ancho,
+        Algoritmo::Confiable,
+        Algoritmo::Horario,
+        Algoritmo::Giros,
+        Algoritmo::Disjuntos,
+        Algoritmo::FlujoMaximo,
+        Algoritmo::FlujoCostoMinimo,
+        Algoritmo::Prim,
+        Algoritmo::Kruskal,
+        Algoritmo::Arborescencia,
+    ];
+
+    fn nombre(self) -> &'static str {
+        match self {
+            Algoritmo::Dijkstra => "Dijkstra",
+            Algoritmo::BellmanFord => "Bellman-Ford",
+            Algoritmo::AEstrella => "A*",
+            Algoritmo::Bidireccional => "Dijkstra bidireccional",
+            Algoritmo::Yen => "k caminos más cortos (Yen)",
+            Algoritmo::VariosOrigenes => "Varios orígenes (Voronoi)",
+            Algoritmo::Paradas => "Con paradas obligatorias",
+            Algoritmo::Presupuesto => "Con presupuesto de recurso",
+            Algoritmo::Pareto => "Frente de Pareto (peso / recurso)",
+            Algoritmo::Ancho => "Camino más ancho (cuello de botella)",
+            Algoritmo::Confiable => "Camino más confiable",
+            Algoritmo::Horario => "Dependiente del horario",
+            Algoritmo::Giros => "Con costos de nodo y giros",
+            Algoritmo::Disjuntos => "Dos caminos disjuntos (Suurballe)",
+            Algoritmo::FlujoMaximo => "Flujo máximo (Dinic)",
+            Algoritmo::FlujoCostoMinimo => "Flujo máximo de costo mínimo",
+            Algoritmo::Prim => "Árbol generador mínimo (Prim)",
+            Algoritmo::Kruskal => "Árbol generador mínimo (Kruskal)",
+            Algoritmo::Arborescencia => "Arborescencia mínima (Chu–Liu/Edmonds)",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TodosPares {
+    Floyd,
+    Johnson,
+}
+
+// (dist, siguiente) de floyd_warshall / johnson
+type Tabla = (Vec<Vec<Option<i32>>>, Vec<Vec<Option<usize>>>);
+
+// un color por origen para pintar la partición, se repiten si hay más de 8
+const PALETA: [egui::Color32; 8] = [
+    egui::Color32::from_rgb(244, 177, 131),
+    egui::Color32::from_rgb(160, 196, 255),
+    egui::Color32::from_rgb(255, 214, 102),
+    egui::Color32::from_rgb(202, 160, 255),
+    egui::Color32::from_rgb(255, 160, 190),
+    egui::Color32::from_rgb(140, 220, 220),
+    egui::Color32::from_rgb(210, 210, 140),
+    egui::Color32::from_rgb(200, 200, 200),
+];
+
+// arriba de esto la tabla ya no entra en pantalla (y son n² celdas por frame)
+const MAX_NODOS_TABLA: usize = 150;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Heuristica {
+    Euclidiana,
+    Manhattan,
+    Cero,
+}
+
 impl Default for DijkstraApp {
     fn default() -> Self {
         Self {
@@ -47,11 +198,44 @@ impl Default for DijkstraApp {
             manual_input: "A B 4\nA C 2\nB D 3\nC D 1\nC B 2\nD E 5".into(),
             origen: 0,
             destino: 1,
+            algoritmo: Algoritmo::Dijkstra,
+            corrido: Algoritmo::Dijkstra,
+            heuristica: Heuristica::Euclidiana,
+            escala: 1.0,
+            parar_en_destino: false,
             grafo: None,
+            coords: vec![],
+            recursos: vec![],
+            presupuesto: 10,
+            horarios: vec![],
+            salida: 0.0,
+            giros: Giros::default(),
+            capacidades: vec![],
+            flujo: None,
+            arbol: None,
+            atenuar_fuera_del_arbol: false,
             log: vec![],
             caminos: vec![],
+            k: 3,
+            sin_nodos_comunes: false,
+            paradas_texto: "C".into(),
+            paradas_en_orden: true,
+            tramos: vec![],
+            ruta_exacta: true,
+            resumen: None,
+            frente: vec![],
+            frente_sel: 0,
+            bloqueos: OpcionesConsulta::default(),
+            origenes_texto: "A C:2".into(),
+            origenes: vec![],
+            fuente: vec![],
+            max_caminos: 50,
+            total_caminos: Some(0),
+            todos_pares: TodosPares::Johnson,
+            tabla: None,
+            ciclo: vec![],
             error: None,
-            labels: gen_labels(16),
+            labels: vec![],
 
             zoom: 1.0,
             mostrar_pesos: true,
@@ -65,23 +249,40 @@ impl Default for DijkstraApp {
 impl DijkstraApp {
     fn construir(&mut self) {
         self.error = None;
-        self.log.clear();
-        self.caminos.clear();
-
-        if self.n < 8 || self.n > 16 {
-            self.error = Some("n debe estar entre 8 y 16".into());
-            return;
-        }
-
-        let labels = self.labels[..self.n].to_vec();
+        self.limpiar_resultado(self.algoritmo);
+        self.tabla = None;
+        self.bloqueos = OpcionesConsulta::default(); // los índices ya no son los mismos
 
-        let g = match self.modo {
+        let (g, coords, recursos, horarios, giros, capacidades) = match self.modo {
             Modo::Aleatorio => {
-                GrafoRandom::new(labels.clone(), self.prob_extra.clamp(0.0, 1.0) as f64).generar()
+                if self.n == 0 {
+                    self.error = Some("n debe ser al menos 1".into());
+                    return;
+                }
+                let labels = gen_labels(self.n);
+                let g = GrafoRandom::new(labels, self.prob_extra.clamp(0.0, 1.0) as f64).generar();
+                let m = g.edge_count();
+                (
+                    g,
+                    vec![None; self.n],
+                    vec![0; m],
+                    vec![None; m],
+                    Giros::default(),
+                    vec![1; m],
+                )
             }
+            // en manual los nodos salen del texto, no de n
             Modo::Manual => {
-                match GrafoManual::new(labels.clone(), self.manual_input.clone()).generar() {
-                    Ok(g) => g,
+                let manual = GrafoManual::new(vec![], self.manual_input.clone());
+                match manual.generar_todo::<i32, i32>() {
+                    Ok(GrafoLeido {
+                        grafo,
+                        coords,
+                        recursos,
+                        horarios,
+                        capacidades,
+                        giros,
+                    }) => (grafo, coords, recursos, horarios, giros, capacidades),
                     Err(e) => {
                         self.error = Some(e);
                         return;
@@ -90,37 +291,578 @@ impl DijkstraApp {
             }
         };
 
+        if g.node_count() == 0 {
+            self.error = Some("El grafo no tiene nodos".into());
+            return;
+        }
+
+        // las etiquetas se sacan del grafo para que siempre coincidan con lo dibujado
+        self.labels = g.node_weights().cloned().collect();
         self.grafo = Some(g);
-        if self.origen >= self.n {
+        self.coords = coords;
+        self.recursos = recursos;
+        self.horarios = horarios;
+        self.giros = giros;
+        self.capacidades = capacidades;
+        let n = self.labels.len();
+        if self.origen >= n {
             self.origen = 0;
         }
-        if self.destino >= self.n {
-            self.destino = (self.n - 1).max(0);
+        if self.destino >= n {
+            self.destino = n - 1;
         }
     }
 
-    fn correr_dijkstra(&mut self) {
+    // muestra el camino del i-ésimo punto del frente de Pareto
+    fn elegir_punto(&mut self, i: usize) {
+        let Some((c, costo, recurso)) = self.frente.get(i) else {
+            return;
+        };
+        self.frente_sel = i;
+        self.caminos = vec![c.clone()];
+        self.total_caminos = Some(1);
+        self.resumen = Some(format!("Costo = {}, recurso = {}", costo, recurso));
+    }
+
+    // cantidad de nodos para los selectores de origen/destino
+    fn nodos_actuales(&self) -> usize {
+        if self.grafo.is_some() {
+            self.labels.len()
+        } else {
+            self.n
+        }
+    }
+
+    // "A(0)" si el índice existe en el grafo construido, si no solo el índice
+    fn nombre_nodo(&self, i: usize) -> String {
+        match self.labels.get(i) {
+            Some(l) => format!("{}({})", l, i),
+            None => format!("{}", i),
+        }
+    }
+
+    // `A B:3 C` -> [(A, 0), (B, 3), (C, 0)], acepta etiquetas o índices
+    fn parsear_origenes(&self) -> Result<Vec<(usize, i32)>, String> {
+        let mut out = vec![];
+        for t in self.origenes_texto.split_whitespace() {
+            let (nodo, desfase) = match t.rsplit_once(':') {
+                Some((nodo, d)) => (
+                    nodo,
+                    d.parse()
+                        .map_err(|_| format!("Desfase inválido en '{}'", t))?,
+                ),
+                None => (t, 0),
+            };
+            out.push((self.buscar_nodo(nodo, "origen")?, desfase));
+        }
+        if out.is_empty() {
+            return Err("Indica al menos un origen".into());
+        }
+        Ok(out)
+    }
+
+    // `C D` -> índices de las paradas, pueden ir vacías
+    fn parsear_paradas(&self) -> Result<Vec<usize>, String> {
+        self.paradas_texto
+            .split_whitespace()
+            .map(|t| self.buscar_nodo(t, "parada"))
+            .collect()
+    }
+
+    // por etiqueta o por índice; `que` es para el mensaje de error
+    fn buscar_nodo(&self, nodo: &str, que: &str) -> Result<usize, String> {
+        match self.labels.iter().position(|l| l == nodo) {
+            Some(i) => Ok(i),
+            None => nodo
+                .parse::<usize>()
+                .ok()
+                .filter(|&i| i < self.labels.len())
+                .ok_or_else(|| format!("La {} '{}' no existe en el grafo", que, nodo)),
+        }
+    }
+
+    fn calcular_tabla(&mut self) {
+        self.error = None;
+        self.limpiar_resultado(Algoritmo::Dijkstra);
+        self.tabla = None;
+        let Some(g) = &self.grafo else {
+            self.error = Some("Primero construye el grafo".into());
+            return;
+        };
+        if g.node_count() > MAX_NODOS_TABLA {
+            self.error = Some(format!(
+                "La tabla es para grafos de hasta {} nodos",
+                MAX_NODOS_TABLA
+            ));
+            return;
+        }
+        let r = match self.todos_pares {
+            TodosPares::Floyd => todos_pares::floyd_warshall(g),
+            TodosPares::Johnson => todos_pares::johnson(g),
+        };
+        match r {
+            Ok(t) => self.tabla = Some(t),
+            Err(FalloBellmanFord::CicloNegativo { ciclo, .. }) => {
+                self.ciclo = ciclo;
+                self.error = Some("Hay un ciclo negativo, no existen caminos mínimos".into());
+            }
+            Err(e) => self.error = Some(e.to_string()),
+        }
+    }
+
+    // Al hacer clic en una celda de la tabla se muestra ese camino sin volver a correr nada
+    fn elegir_celda(&mut self, i: usize, j: usize) {
+        let Some((_, siguiente)) = &self.tabla else {
+            return;
+        };
+        let camino = todos_pares::camino_siguiente(siguiente, i, j);
+        self.origen = i;
+        self.destino = j;
+        // el camino de la tabla es uno mínimo, se muestra como los de Dijkstra
+        self.limpiar_resultado(Algoritmo::Dijkstra);
+        self.caminos = camino.into_iter().collect();
+        self.total_caminos = Some(self.caminos.len() as u128);
+    }
+
+    // Borra lo que dejó la corrida anterior; lo que se calcule ahora es de `corrido`
+    fn limpiar_resultado(&mut self, corrido: Algoritmo) {
+        self.corrido = corrido;
         self.log.clear();
         self.caminos.clear();
+        self.total_caminos = Some(0);
+        self.ciclo.clear();
+        self.fuente.clear();
+        self.tramos.clear();
+        self.resumen = None;
+        self.frente.clear();
+        self.flujo = None;
+        self.arbol = None;
+    }
+
+    fn correr_dijkstra(&mut self) {
+        self.error = None;
+        self.limpiar_resultado(self.algoritmo);
         if self.grafo.is_none() {
             self.error = Some("Primero construye el grafo".into());
             return;
         }
+        match self.algoritmo {
+            Algoritmo::Dijkstra | Algoritmo::AEstrella | Algoritmo::BellmanFord => {
+                self.correr_caminos_minimos()
+            }
+            Algoritmo::Bidireccional => self.correr_bidireccional(),
+            Algoritmo::VariosOrigenes => self.correr_multiorigen(),
+            Algoritmo::Paradas => self.correr_paradas(),
+            Algoritmo::Presupuesto => self.correr_presupuesto(),
+            Algoritmo::Pareto => self.correr_pareto(),
+            Algoritmo::Ancho | Algoritmo::Confiable | Algoritmo::Horario => {
+                self.correr_semianillo()
+            }
+            Algoritmo::Giros => self.correr_giros(),
+            Algoritmo::Disjuntos => self.correr_disjuntos(),
+            Algoritmo::FlujoMaximo | Algoritmo::FlujoCostoMinimo => self.correr_flujo(),
+            Algoritmo::Prim | Algoritmo::Kruskal | Algoritmo::Arborescencia => self.correr_arbol(),
+            Algoritmo::Yen => self.correr_yen(),
+        }
+    }
+
+    // el bidireccional ya devuelve los caminos, no hay preds que reconstruir
+    fn correr_bidireccional(&mut self) {
         let g = self.grafo.as_ref().unwrap();
-        let (dist, preds, pasos) = dijkstra::dijkstra_detallado(g, self.origen);
+        match bidireccional::dijkstra_bidireccional(g, self.origen, self.destino) {
+            Ok((total, caminos, eventos)) => {
+                self.log = dijkstra::formatear_log(&eventos);
+                if total.is_none() {
+                    self.log.push(
+                        "Destino no alcanzable desde el origen indicado, intente otro destino."
+                            .into(),
+                    );
+                }
+                self.total_caminos = Some(caminos.len() as u128);
+                self.caminos = caminos;
+                self.caminos.truncate(self.max_caminos);
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // cada nodo queda con el origen que lo atiende, el camino que se muestra es el del destino
+    fn correr_multiorigen(&mut self) {
+        let origenes = match self.parsear_origenes() {
+            Ok(o) => o,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            }
+        };
+        let g = self.grafo.as_ref().unwrap();
+        match dijkstra::dijkstra_multiorigen(g, &origenes) {
+            Ok((dist, preds, fuente, pasos)) => {
+                self.log = pasos;
+                if let Some(f) = fuente[self.destino] {
+                    self.caminos = dijkstra::reconstruir_caminos_limitado(
+                        &preds,
+                        f,
+                        self.destino,
+                        self.max_caminos,
+                    );
+                    self.total_caminos = dijkstra::contar_caminos(&preds, f, self.destino);
+                    self.log.push(format!(
+                        "{} atiende a {} con costo {}",
+                        self.nombre_nodo(f),
+                        self.nombre_nodo(self.destino),
+                        dist[self.destino].unwrap()
+                    ));
+                } else {
+                    self.log.push(
+                        "Ningún origen alcanza el destino indicado, intente otro destino.".into(),
+                    );
+                }
+                self.origenes = origenes;
+                self.fuente = fuente
+                    .into_iter()
+                    .zip(dist)
+                    .map(|(f, d)| f.zip(d))
+                    .collect();
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // la ruta completa va como único camino, el desglose por tramo queda en self.tramos
+    fn correr_paradas(&mut self) {
+        let lista = match self.parsear_paradas() {
+            Ok(p) => p,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            }
+        };
+        let g = self.grafo.as_ref().unwrap();
+        let (o, d) = (self.origen, self.destino);
+        match paradas::ruta_con_paradas(g, o, d, &lista, self.paradas_en_orden) {
+            Ok(Some(ruta)) => {
+                for (i, t) in ruta.tramos.iter().enumerate() {
+                    self.log.push(format!(
+                        "Tramo {}: {} -> {} costo {}",
+                        i + 1,
+                        self.nombre_nodo(t.desde),
+                        self.nombre_nodo(t.hasta),
+                        t.costo
+                    ));
+                }
+                self.log.push(format!("Total: {}", ruta.total));
+                self.caminos = vec![ruta.camino];
+                self.total_caminos = Some(1);
+                self.tramos = ruta.tramos;
+                self.ruta_exacta = ruta.exacta;
+            }
+            Ok(None) => self
+                .log
+                .push("No hay ruta que pase por todas las paradas, intente con otras.".into()),
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // un solo camino, el costo y el recurso gastado van al log
+    fn correr_presupuesto(&mut self) {
+        let g = self.grafo.as_ref().unwrap();
+        let (o, d) = (self.origen, self.destino);
+        match presupuesto::camino_con_presupuesto(g, &self.recursos, o, d, self.presupuesto) {
+            Ok(Some((camino, costo, recurso))) => {
+                self.log.push(format!(
+                    "Costo {}, recurso {} de {}",
+                    costo, recurso, self.presupuesto
+                ));
+                self.caminos = vec![camino];
+                self.total_caminos = Some(1);
+                self.resumen = Some(format!("Costo = {}, recurso = {}", costo, recurso));
+            }
+            Ok(None) => self
+                .log
+                .push("Ningún camino entra en el presupuesto, intente con uno mayor.".into()),
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // el frente completo queda en self.frente, el camino que se ve es el del punto elegido
+    fn correr_pareto(&mut self) {
+        let g = self.grafo.as_ref().unwrap();
+        let (o, d) = (self.origen, self.destino);
+        match presupuesto::frente_pareto(g, &self.recursos, o, d) {
+            Ok(frente) => {
+                for (i, (c, costo, recurso)) in frente.iter().enumerate() {
+                    self.log.push(format!(
+                        "Punto {}: costo {}, recurso {}, {} nodos",
+                        i + 1,
+                        costo,
+                        recurso,
+                        c.len()
+                    ));
+                }
+                if frente.is_empty() {
+                    self.log.push(
+                        "Destino no alcanzable desde el origen indicado, intente otro destino."
+                            .into(),
+                    );
+                }
+                self.frente = frente;
+                self.elegir_punto(0);
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // mismos caminos empatados que Dijkstra, pero el valor va aparte (no es la suma)
+    fn correr_semianillo(&mut self) {
+        let g = self.grafo.as_ref().unwrap();
+        let (o, d) = (self.origen, self.destino);
+        let resultado = if self.algoritmo == Algoritmo::Ancho {
+            let (cap, preds, log) = semianillos::camino_mas_ancho(g, o);
+            Ok((cap[d].map(|c| format!("Capacidad = {}", c)), preds, log))
+        } else if self.algoritmo == Algoritmo::Horario {
+            let salida = self.salida;
+            horario::dijkstra_con_horario(g, &self.horarios, o, salida).map(|(ll, preds, log)| {
+                let resumen = ll[d].map(|t| {
+                    format!(
+                        "Sale en {}, llega en {} ({} de viaje)",
+                        salida,
+                        t,
+                        t.0 - salida
+                    )
+                });
+                (resumen, preds, log)
+            })
+        } else {
+            // los pesos de la interfaz son enteros, aquí se leen como porcentaje
+            match g
+                .edge_references()
+                .find(|e| !(0..=100).contains(e.weight()))
+            {
+                Some(e) => Err(format!(
+                    "En este modo cada peso es una probabilidad en %, entre 0 y 100: la arista {} -> {} tiene {}",
+                    self.nombre_nodo(e.source().index()),
+                    self.nombre_nodo(e.target().index()),
+                    e.weight()
+                )),
+                None => {
+                    let gp = g.map(|_, l| l.clone(), |_, &w| Real(w as f64 / 100.0));
+                    semianillos::camino_mas_confiable(&gp, o).map(|(prob, preds, log)| {
+                        (prob[d].map(|p| format!("Probabilidad = {}", p)), preds, log)
+                    })
+                }
+            }
+        };
+        match resultado {
+            Ok((resumen, preds, log)) => {
+                self.log = log;
+                if resumen.is_none() {
+                    self.log.push(
+                        "Destino no alcanzable desde el origen indicado, intente otro destino."
+                            .into(),
+                    );
+                } else {
+                    self.caminos =
+                        dijkstra::reconstruir_caminos_limitado(&preds, o, d, self.max_caminos);
+                    self.total_caminos = dijkstra::contar_caminos(&preds, o, d);
+                }
+                self.resumen = resumen;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // un solo camino, que puede repetir nodos para no hacer un giro prohibido
+    fn correr_giros(&mut self) {
+        let g = self.grafo.as_ref().unwrap();
+        let d = self.destino;
+        match giros::dijkstra_con_giros(g, &self.giros, self.origen) {
+            Ok(r) => {
+                let camino = r.camino(d);
+                self.log = r.log;
+                match (r.dist[d], camino) {
+                    (Some(costo), Some(camino)) => {
+                        self.caminos = vec![camino];
+                        self.total_caminos = Some(1);
+                        self.resumen = Some(format!("Costo con nodos y giros = {}", costo));
+                    }
+                    _ => self.log.push(
+                        "Destino no alcanzable desde el origen indicado, intente otro destino."
+                            .into(),
+                    ),
+                }
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // dos caminos de distinto costo, el panel muestra la suma de cada uno y el dibujo los pinta
+    // de colores distintos
+    fn correr_disjuntos(&mut self) {
+        let g = self.grafo.as_ref().unwrap();
+        let (o, d) = (self.origen, self.destino);
+        match disjuntos::caminos_disjuntos(g, o, d, self.sin_nodos_comunes) {
+            Ok((par, log)) => {
+                self.log = log;
+                match par {
+                    Some([(c1, w1), (c2, w2)]) => {
+                        self.caminos = vec![c1, c2];
+                        self.total_caminos = Some(2);
+                        self.resumen = Some(format!("Total = {}", w1 as i64 + w2 as i64));
+                    }
+                    None => self
+                        .log
+                        .push("No hay dos caminos disjuntos, intente otro destino.".into()),
+                }
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // el flujo no da caminos: el dibujo muestra flujo/capacidad en cada arista y el corte mínimo
+    fn correr_flujo(&mut self) {
+        let g = self.grafo.as_ref().unwrap();
+        let (o, d) = (self.origen, self.destino);
+        let resultado = if self.algoritmo == Algoritmo::FlujoMaximo {
+            flujo::flujo_maximo(g, &self.capacidades, o, d)
+        } else {
+            flujo::flujo_costo_minimo(g, &self.capacidades, o, d)
+        };
+        match resultado {
+            Ok(mut f) => {
+                self.log = std::mem::take(&mut f.log);
+                self.flujo = Some(f);
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // los árboles generadores no dan caminos: el dibujo resalta sus aristas y atenúa el resto
+    fn correr_arbol(&mut self) {
+        let g = self.grafo.as_ref().unwrap();
+        let resultado = match self.algoritmo {
+            Algoritmo::Prim => arbol::prim(g),
+            Algoritmo::Kruskal => arbol::kruskal(g),
+            _ => arbol::arborescencia_minima(g, self.origen),
+        };
+        match resultado {
+            Ok((aristas, total, log)) => {
+                self.log = log;
+                self.resumen = Some(format!("Peso total = {}", total));
+                self.arbol = Some(aristas);
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // yen da caminos de distinto costo, el panel ya muestra la suma de cada uno
+    fn correr_yen(&mut self) {
+        let g = self.grafo.as_ref().unwrap();
+        match yen::k_caminos_mas_cortos(g, self.origen, self.destino, self.k) {
+            Ok(caminos) => {
+                for (i, (c, costo)) in caminos.iter().enumerate() {
+                    self.log.push(format!(
+                        "Camino {}: {} nodos, costo {}",
+                        i + 1,
+                        c.len(),
+                        costo
+                    ));
+                }
+                if caminos.is_empty() {
+                    self.log.push(
+                        "Destino no alcanzable desde el origen indicado, intente otro destino."
+                            .into(),
+                    );
+                }
+                self.caminos = caminos.into_iter().map(|(c, _)| c).collect();
+                self.total_caminos = Some(self.caminos.len() as u128);
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    // Dijkstra, A* y Bellman-Ford: distancias a todos los nodos y los caminos empatados al destino
+    fn correr_caminos_minimos(&mut self) {
+        let g = self.grafo.as_ref().unwrap();
+        let resultado = match self.algoritmo {
+            Algoritmo::Dijkstra => {
+                let opciones = OpcionesConsulta {
+                    hasta: self.parar_en_destino.then_some(self.destino),
+                    ..self.bloqueos.clone()
+                };
+                dijkstra::dijkstra_detallado_con(g, self.origen, &opciones)
+            }
+            Algoritmo::AEstrella => {
+                let (o, d, c) = (self.origen, self.destino, &self.coords);
+                match self.heuristica {
+                    Heuristica::Euclidiana => {
+                        astar::astar_detallado(g, o, d, astar::euclidiana(c, d, self.escala))
+                    }
+                    Heuristica::Manhattan => {
+                        astar::astar_detallado(g, o, d, astar::manhattan(c, d, self.escala))
+                    }
+                    Heuristica::Cero => astar::astar_detallado(g, o, d, |_| 0),
+                }
+            }
+            // solo queda Bellman-Ford, los demás van a su propio correr_*
+            _ => match bellman_ford::bellman_ford_detallado(g, self.origen) {
+                Ok(r) => Ok(r),
+                Err(FalloBellmanFord::CicloNegativo { ciclo, log }) => {
+                    self.log = log;
+                    self.ciclo = ciclo;
+                    self.error = Some("Hay un ciclo negativo, no existen caminos mínimos".into());
+                    return;
+                }
+                Err(e) => Err(e.to_string()),
+            },
+        };
+        let (dist, preds, pasos) = match resultado {
+            Ok(r) => r,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            }
+        };
         self.log = pasos;
 
-        if dist[self.destino].is_none() {
+        // dist y preds pasan al árbol sin copiarlos, los caminos se sacan de ahí
+        let a = ArbolCaminos::new(g, self.origen, dist, preds, vec![]);
+
+        // DAG de caminos mínimos (con empates hay más de una arista por nodo), para atenuar el resto
+        if self.algoritmo == Algoritmo::Dijkstra {
+            self.arbol = Some(a.aristas_dag().map(|(_, _, id)| id).collect());
+        }
+
+        if !a.es_alcanzable(self.destino) {
             self.log.push(
                 "Destino no alcanzable desde el origen indicado, intente otro destino.".into(),
             );
             return;
         }
 
-        let todas = dijkstra::reconstruir_todos_caminos(&preds, self.origen, self.destino);
-        self.caminos = todas;
+        // en grillas los empates crecen exponencialmente, así que solo se arman los primeros
+        self.caminos = dijkstra::reconstruir_caminos_limitado(
+            a.preds(),
+            self.origen,
+            self.destino,
+            self.max_caminos,
+        );
+        self.total_caminos = dijkstra::contar_caminos(a.preds(), self.origen, self.destino);
+    }
+}
+// 1048576 -> "1,048,576"
+fn con_miles(n: u128) -> String {
+    let s = n.to_string();
+    let mut out = String::new();
+    for (i, c) in s.chars().enumerate() {
+        if i > 0 && (s.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
     }
+    out
 }
+
 // inicializador de la interfaz
 impl App for DijkstraApp {
     fn update(&mut self, ctx: &egui::Context, _: &mut eframe::Frame) {
@@ -160,14 +902,17 @@ impl App for DijkstraApp {
             .resizable(true)
             .show(ctx, |ui| {
                 ui.label("Parámetros");
-                ui.add(egui::Slider::new(&mut self.n, 8..=16).text("n (nodos)"));
-
                 ui.horizontal(|ui| {
                     ui.radio_value(&mut self.modo, Modo::Aleatorio, "Aleatorio");
                     ui.radio_value(&mut self.modo, Modo::Manual, "Manual");
                 });
 
                 if self.modo == Modo::Aleatorio {
+                    ui.add(
+                        egui::Slider::new(&mut self.n, 1..=5000)
+                            .logarithmic(true)
+                            .text("n (nodos)"),
+                    );
                     ui.add(
                         egui::Slider::new(&mut self.prob_extra, 0.0..=1.0)
                             .text("Prob. extra de arista"),
@@ -180,19 +925,159 @@ impl App for DijkstraApp {
                             .font(egui::TextStyle::Monospace),
                     );
                     ui.small("Ejemplo: A B 4  (A→B con peso 4)");
+                    ui.small("Con recurso opcional (tiempo, peaje...): A B 4 2");
+                    ui.small("Con horario opcional (salida=duración): A B 4 0=4 60=10");
+                    ui.small("Los nodos se crean al mencionarlos, con espacios van entre comillas: \"San Isidro\" Lima 3");
+                    ui.small("Para declarar nodos sueltos: nodos: X Y Z");
                 }
 
                 ui.separator();
                 ui.label("Origen / Destino");
+                let max_idx = self.nodos_actuales().saturating_sub(1);
                 ui.horizontal(|ui| {
-                    ui.add(
-                        egui::DragValue::new(&mut self.origen).range(0..=self.n.saturating_sub(1)),
-                    );
-                    ui.add(
-                        egui::DragValue::new(&mut self.destino).range(0..=self.n.saturating_sub(1)),
-                    );
+                    ui.add(egui::DragValue::new(&mut self.origen).range(0..=max_idx));
+                    ui.add(egui::DragValue::new(&mut self.destino).range(0..=max_idx));
                 });
-                ui.small("Los índices comienzan en 0. A=0, B=1, ...");
+                if self.grafo.is_some() {
+                    ui.small(format!(
+                        "{} → {}",
+                        self.nombre_nodo(self.origen),
+                        self.nombre_nodo(self.destino)
+                    ));
+                }
+                ui.small("Los índices comienzan en 0. A=0, B=1, ..., Z=25, AA=26, ...");
+
+                ui.separator();
+                ui.label("Algoritmo");
+                egui::ComboBox::from_id_source("algoritmo")
+                    .selected_text(self.algoritmo.nombre())
+                    .show_ui(ui, |ui| {
+                        for a in Algoritmo::TODOS {
+                            ui.selectable_value(&mut self.algoritmo, a, a.nombre());
+                        }
+                    });
+                match self.algoritmo {
+                    Algoritmo::Dijkstra => {
+                        ui.checkbox(&mut self.parar_en_destino, "Parar al fijar el destino");
+                        ui.checkbox(
+                            &mut self.atenuar_fuera_del_arbol,
+                            "Atenuar lo que no es del árbol de caminos mínimos",
+                        );
+                        ui.small("Clic en un nodo o arista del gráfico para bloquearlo o desbloquearlo.");
+                    }
+                    Algoritmo::Bidireccional => {
+                        ui.small("Busca desde el origen y desde el destino a la vez.");
+                    }
+                    Algoritmo::BellmanFord => {
+                        ui.small("Acepta pesos negativos y detecta ciclos negativos.");
+                    }
+                    Algoritmo::VariosOrigenes => {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.origenes_texto)
+                                .font(egui::TextStyle::Monospace),
+                        );
+                        ui.small("Orígenes separados por espacios, con desfase opcional: A B:3 C");
+                        ui.small("Cada nodo se pinta del color del origen que lo atiende.");
+                    }
+                    Algoritmo::Paradas => {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.paradas_texto)
+                                .font(egui::TextStyle::Monospace),
+                        );
+                        ui.small("Paradas separadas por espacios, entre el origen y el destino.");
+                        ui.checkbox(&mut self.paradas_en_orden, "En el orden dado");
+                        if !self.paradas_en_orden {
+                            ui.small(format!(
+                                "Orden óptimo hasta {} paradas, con más se usa una heurística.",
+                                paradas::MAX_PARADAS_EXACTO
+                            ));
+                        }
+                    }
+                    Algoritmo::Presupuesto => {
+                        ui.add(egui::DragValue::new(&mut self.presupuesto).range(0..=i32::MAX).prefix("presupuesto: "));
+                        ui.small("El recurso de cada arista es la cuarta columna del modo manual: A B 4 2 (0 si falta).");
+                    }
+                    Algoritmo::Pareto => {
+                        ui.small("Segundo criterio: la cuarta columna del modo manual, A B 4 2 (0 si falta).");
+                        ui.small("Clic en un punto del diagrama para ver su camino.");
+                    }
+                    Algoritmo::Ancho => {
+                        ui.small("Maximiza la arista más chica del camino (capacidad, ancho de banda).");
+                    }
+                    Algoritmo::Confiable => {
+                        ui.small("Cada peso es la probabilidad de la arista en %, de 0 a 100: 90 = 0.9.");
+                        ui.small("Maximiza el producto de las probabilidades.");
+                    }
+                    Algoritmo::Horario => {
+                        ui.add(egui::DragValue::new(&mut self.salida).speed(1.0).prefix("salida: "));
+                        ui.small("Horario por arista en el modo manual: A B 5 0=5 60=15 (salida=duración).");
+                        ui.small("Las aristas sin horario tardan siempre su peso.");
+                    }
+                    Algoritmo::FlujoMaximo | Algoritmo::FlujoCostoMinimo => {
+                        ui.small("Capacidad por arista en el modo manual: A B 4 cap=10 (1 si falta).");
+                        if self.algoritmo == Algoritmo::FlujoCostoMinimo {
+                            ui.small("El peso es el costo por unidad de flujo.");
+                        }
+                        ui.small("Cada arista muestra flujo/capacidad; en rojo el corte mínimo.");
+                    }
+                    Algoritmo::Prim | Algoritmo::Kruskal => {
+                        ui.small("Lee las aristas sin dirección; si el grafo no es conexo da un bosque.");
+                        ui.small("Minimiza el peso total, no la distancia desde el origen como Dijkstra.");
+                    }
+                    Algoritmo::Arborescencia => {
+                        ui.small("Desde el origen respetando las direcciones, una arista de entrada por nodo.");
+                        ui.small("Los nodos que no se alcanzan desde el origen quedan afuera.");
+                    }
+                    Algoritmo::Disjuntos => {
+                        ui.checkbox(&mut self.sin_nodos_comunes, "Sin nodos en común");
+                        ui.small("El par de caminos sin aristas en común más barato, para tener respaldo.");
+                    }
+                    Algoritmo::Giros => {
+                        ui.small("En el modo manual: costo: B 2 (pasar por B), giro: A B C 3 (doblar de A -> B a B -> C).");
+                        ui.small("prohibido: A B C no deja ir de A -> B a B -> C.");
+                    }
+                    Algoritmo::Yen => {
+                        ui.add(egui::DragValue::new(&mut self.k).range(1..=1000).prefix("k: "));
+                        ui.small("Los k caminos sin nodos repetidos más baratos, aunque no empaten.");
+                    }
+                    Algoritmo::AEstrella => {
+                        ui.horizontal(|ui| {
+                            ui.radio_value(&mut self.heuristica, Heuristica::Euclidiana, "Euclídea");
+                            ui.radio_value(&mut self.heuristica, Heuristica::Manhattan, "Manhattan");
+                            ui.radio_value(&mut self.heuristica, Heuristica::Cero, "h = 0");
+                        });
+                        ui.add(
+                            egui::DragValue::new(&mut self.escala)
+                                .speed(0.05)
+                                .range(0.0..=f64::MAX)
+                                .prefix("escala: "),
+                        );
+                        ui.small("Usa las coordenadas `pos: U x y` del modo manual.");
+                        if self.grafo.is_some() && !astar::coordenadas_completas(&self.coords) {
+                            ui.small("No todos los nodos tienen `pos:`, así que h = 0 (como Dijkstra).");
+                        }
+                    }
+                }
+
+                ui.add(
+                    egui::DragValue::new(&mut self.max_caminos)
+                        .range(1..=10_000)
+                        .prefix("máx. caminos: "),
+                );
+
+                let n_bloqueos =
+                    self.bloqueos.nodos_bloqueados.len() + self.bloqueos.aristas_bloqueadas.len();
+                if n_bloqueos > 0 {
+                    ui.horizontal(|ui| {
+                        ui.small(format!("{} bloqueos", n_bloqueos));
+                        if ui.small_button("Desbloquear todo").clicked() {
+                            self.bloqueos = OpcionesConsulta::default();
+                        }
+                    });
+                    if self.algoritmo != Algoritmo::Dijkstra {
+                        ui.small("Los bloqueos solo se aplican con Dijkstra.");
+                    }
+                }
 
                 ui.separator();
                 ui.label("Gráfico");
@@ -203,10 +1088,24 @@ impl App for DijkstraApp {
                 if ui.button("Construir grafo").clicked() {
                     self.construir();
                 }
-                if ui.button("Correr Dijkstra").clicked() {
+                if ui
+                    .button(format!("Correr {}", self.algoritmo.nombre()))
+                    .clicked()
+                {
                     self.correr_dijkstra();
                 }
 
+                ui.separator();
+                ui.label("Todos los pares");
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.todos_pares, TodosPares::Floyd, "Floyd-Warshall");
+                    ui.radio_value(&mut self.todos_pares, TodosPares::Johnson, "Johnson");
+                });
+                ui.small("Floyd-Warshall para grafos chicos o densos, Johnson para dispersos.");
+                if ui.button("Calcular tabla de distancias").clicked() {
+                    self.calcular_tabla();
+                }
+
                 if let Some(err) = &self.error {
                     ui.colored_label(egui::Color32::RED, err);
                 }
@@ -223,9 +1122,30 @@ impl App for DijkstraApp {
                             let width = ui.available_width();
                             let height = (width * 0.75).clamp(320.0, 900.0);
                             let desired = egui::vec2(width, height);
-                            let (rect, resp) = ui.allocate_at_least(desired, egui::Sense::drag());
+                            let (total, _) = ui.allocate_at_least(desired, egui::Sense::hover());
+                            // con frente de Pareto el diagrama de dispersión va a la derecha del grafo
+                            let (rect, rect_frente) = if self.frente.is_empty() {
+                                (total, None)
+                            } else {
+                                let (a, b) = total.split_left_right_at_fraction(0.68);
+                                (a, Some(b.shrink2(egui::vec2(6.0, 0.0))))
+                            };
+                            let resp = ui.interact(rect, ui.id().with("grafo"), egui::Sense::click_and_drag());
                             let painter = ui.painter_at(rect);
 
+                            if let Some(rf) = rect_frente {
+                                let puntos: Vec<(i32, i32)> =
+                                    self.frente.iter().map(|&(_, c, r)| (c, r)).collect();
+                                let resp_frente = ui.interact(rf, ui.id().with("frente"), egui::Sense::click());
+                                if resp_frente.clicked()
+                                    && let Some(p) = resp_frente.interact_pointer_pos()
+                                    && let Some(i) = dibujar::punto_frente_en(rf, &puntos, p)
+                                {
+                                    self.elegir_punto(i);
+                                }
+                                dibujar::draw_frente(&ui.painter_at(rf), rf, &puntos, Some(self.frente_sel));
+                            }
+
                             // Pan: arrastrar con mouse
                             if resp.dragged() {
                                 if let Some(pos) = ui.input(|i| i.pointer.interact_pos()) {
@@ -251,17 +1171,100 @@ impl App for DijkstraApp {
                                 }
                             }
 
+                            // Clic: bloquear/desbloquear el nodo o arista bajo el puntero
+                            if resp.clicked()
+                                && let (Some(g), Some(p)) = (&self.grafo, resp.interact_pointer_pos())
+                            {
+                                match dibujar::elemento_en(rect, g, self.zoom, self.offset, p) {
+                                    Some(dibujar::Elemento::Nodo(v)) => self.bloqueos.alternar_nodo(v),
+                                    Some(dibujar::Elemento::Arista(u, v)) => {
+                                        self.bloqueos.alternar_arista(u, v)
+                                    }
+                                    None => {}
+                                }
+                            }
+
                             if let Some(g) = &self.grafo {
-                                let labels_now = self.labels[..self.n].to_vec();
+                                let mut resaltado = dibujar::Resaltado::default();
+                                for (i, c) in self.caminos.iter().enumerate() {
+                                    // el segundo camino del par disjunto va en otro color
+                                    let color = if self.corrido == Algoritmo::Disjuntos && i == 1 {
+                                        egui::Color32::from_rgb(255, 170, 60)
+                                    } else {
+                                        egui::Color32::from_rgb(70, 130, 220)
+                                    };
+                                    resaltado.camino(g, c, color);
+                                }
+                                // cada tramo de la ruta con paradas en su color
+                                for (i, t) in self.tramos.iter().enumerate() {
+                                    resaltado.camino(g, &t.camino, PALETA[i % PALETA.len()]);
+                                }
+                                resaltado.camino(g, &self.ciclo, egui::Color32::RED);
+                                // aristas con flujo en azul, el corte mínimo en rojo y el lado del
+                                // origen relleno
+                                if let Some(f) = &self.flujo {
+                                    for e in g.edge_references() {
+                                        let id = e.id().index();
+                                        resaltado.etiquetas.insert(
+                                            id,
+                                            format!("{}/{}", f.por_arista[id], self.capacidades[id]),
+                                        );
+                                        if f.por_arista[id] > 0 {
+                                            resaltado
+                                                .aristas
+                                                .insert(id, egui::Color32::from_rgb(70, 130, 220));
+                                        }
+                                    }
+                                    for &id in &f.corte {
+                                        resaltado.aristas.insert(id, egui::Color32::RED);
+                                    }
+                                    for (v, &lado) in f.lado_origen.iter().enumerate() {
+                                        if lado {
+                                            resaltado.relleno.insert(v, PALETA[1]);
+                                        }
+                                    }
+                                }
+                                // el árbol generador en verde; con Dijkstra el árbol de caminos
+                                // mínimos solo se usa para atenuar lo demás
+                                if let Some(a) = &self.arbol {
+                                    let generador = self.corrido != Algoritmo::Dijkstra;
+                                    if generador {
+                                        for &id in a {
+                                            resaltado
+                                                .aristas
+                                                .insert(id, egui::Color32::from_rgb(90, 190, 110));
+                                        }
+                                    }
+                                    if generador || self.atenuar_fuera_del_arbol {
+                                        resaltado.arbol = Some(a.iter().copied().collect());
+                                    }
+                                }
+                                for (v, f) in self.fuente.iter().enumerate() {
+                                    if let Some(pos) = f.and_then(|(f, _)| {
+                                        self.origenes.iter().position(|&(o, _)| o == f)
+                                    }) {
+                                        resaltado.relleno.insert(v, PALETA[pos % PALETA.len()]);
+                                    }
+                                }
+                                // lo bloqueado va al final para que se vea encima de los caminos
+                                let bloqueado = egui::Color32::from_rgb(150, 40, 40);
+                                for &v in &self.bloqueos.nodos_bloqueados {
+                                    resaltado.nodos.insert(v, bloqueado);
+                                    resaltado.relleno.insert(v, egui::Color32::from_gray(120));
+                                }
+                                for &(u, v) in &self.bloqueos.aristas_bloqueadas {
+                                    resaltado.par(g, u, v, bloqueado);
+                                }
                                 dibujar::draw_graph_offset(
                                     ui,
                                     &painter,
                                     rect,
                                     g,
-                                    &labels_now,
+                                    &self.labels,
                                     self.zoom,
                                     self.mostrar_pesos,
                                     self.offset,
+                                    &resaltado,
                                 );
                             } else {
                                 ui.centered_and_justified(|ui| {
@@ -270,46 +1273,103 @@ impl App for DijkstraApp {
                             }
                         });
 
+                    // === Nodos ===
+                    egui::CollapsingHeader::new("Nodos del grafo")
+                        .default_open(false)
+                        .show(ui, |ui| {
+                            if self.grafo.is_some() {
+                                ui.label(format!("{} nodos", self.labels.len()));
+                                ui.monospace(
+                                    (0..self.labels.len())
+                                        .map(|i| self.nombre_nodo(i))
+                                        .collect::<Vec<_>>()
+                                        .join("  "),
+                                );
+                            } else {
+                                ui.label("Aún no has construido el grafo.");
+                            }
+                        });
+
                     // === Aristas ===
                     egui::CollapsingHeader::new("Aristas del grafo")
-                        .default_open(true)
+                        .default_open(false)
                         .show(ui, |ui| {
                             if let Some(g) = &self.grafo {
-                                for e in g.edge_references() {
-                                    let u = e.source().index();
-                                    let v = e.target().index();
-                                    let w = *e.weight();
-                                    ui.monospace(format!(
-                                        "{}({}) -> {}({})  peso={}",
-                                        self.labels[u], u, self.labels[v], v, w
-                                    ));
-                                }
+                                // solo se arman las filas visibles, con miles de aristas listarlas
+                                // todas cada frame congela la interfaz
+                                let alto = ui.text_style_height(&egui::TextStyle::Monospace);
+                                egui::ScrollArea::vertical()
+                                    .id_source("aristas_del_grafo")
+                                    .max_height(300.0)
+                                    .show_rows(ui, alto, g.edge_count(), |ui, filas| {
+                                        for e in &g.raw_edges()[filas] {
+                                            let u = e.source().index();
+                                            let v = e.target().index();
+                                            ui.monospace(format!(
+                                                "{}({}) -> {}({})  peso={}",
+                                                self.labels[u], u, self.labels[v], v, e.weight
+                                            ));
+                                        }
+                                    });
                             } else {
                                 ui.label("Aún no has construido el grafo.");
                             }
                         });
 
                     // === Paso a paso ===
-                    egui::CollapsingHeader::new("Paso a paso (Dijkstra)")
-                        .default_open(true)
-                        .show(ui, |ui| {
-                            if self.log.is_empty() {
-                                ui.label("Sin ejecuciones aún.");
-                            } else {
-                                for l in &self.log {
-                                    ui.monospace(l);
-                                }
+                    egui::CollapsingHeader::new(format!(
+                        "Paso a paso ({})",
+                        self.corrido.nombre()
+                    ))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        if self.log.is_empty() {
+                            ui.label("Sin ejecuciones aún.");
+                        } else {
+                            for l in &self.log {
+                                ui.monospace(l);
                             }
-                        });
+                        }
+                    });
 
                     // === Caminos mínimos ===
                     egui::CollapsingHeader::new("Caminos mínimos")
                         .default_open(true)
                         .show(ui, |ui| {
-                            if self.caminos.is_empty() {
+                            if !self.ciclo.is_empty() {
+                                let texto = self
+                                    .ciclo
+                                    .iter()
+                                    .map(|&idx| format!("{}({})", self.labels[idx], idx))
+                                    .collect::<Vec<_>>()
+                                    .join(" -> ");
+                                ui.colored_label(
+                                    egui::Color32::RED,
+                                    format!("Ciclo negativo: {}", texto),
+                                );
+                            } else if self.caminos.is_empty() {
                                 ui.label("Sin caminos para mostrar.");
+                                // los árboles generadores no dan caminos pero sí un peso total
+                                if let Some(r) = &self.resumen {
+                                    ui.label(r);
+                                }
                             //else
                             } else {
+                                let mostrados = self.caminos.len() as u128;
+                                match self.total_caminos {
+                                    Some(t) if t > mostrados => ui.label(format!(
+                                        "Mostrando {} de {}",
+                                        mostrados,
+                                        con_miles(t)
+                                    )),
+                                    Some(t) => ui.label(format!("{} caminos", con_miles(t))),
+                                    None => ui.label(format!(
+                                        "Mostrando {} de más de {}",
+                                        mostrados,
+                                        con_miles(u128::MAX)
+                                    )),
+                                };
+
                                 // Necesitamos el grafo para sumar los pesos
                                 let g = self.grafo.as_ref().unwrap();
 
@@ -321,30 +1381,166 @@ impl App for DijkstraApp {
                                         .collect::<Vec<_>>()
                                         .join(" -> ");
 
-                                    // Suma de pesos del camino
-                                    let mut total_peso: i32 = 0;
-                                    for win in c.windows(2) {
-                                        let u = win[0];
-                                        let v = win[1];
-                                        if let Some(eid) = g.find_edge(
-                                            petgraph::prelude::NodeIndex::new(u),
-                                            petgraph::prelude::NodeIndex::new(v),
-                                        ) {
-                                            if let Some(w) = g.edge_weight(eid) {
-                                                total_peso += *w;
-                                            }
-                                        }
+                                    // en ancho/confiable/horario/giros el valor del camino va en el resumen de abajo
+                                    if matches!(
+                                        self.corrido,
+                                        Algoritmo::Ancho
+                                            | Algoritmo::Confiable
+                                            | Algoritmo::Horario
+                                            | Algoritmo::Giros
+                                    ) {
+                                        ui.monospace(format!("{}: {}", i + 1, texto));
+                                        continue;
                                     }
 
+                                    // con paralelas cuenta la más barata, como los solvers
+                                    let total = match dijkstra::costo_camino(g, c) {
+                                        Ok(w) => w.to_string(),
+                                        Err(e) => e,
+                                    };
                                     ui.monospace(format!(
                                         "{}: {}   |   suma de pesos = {}",
                                         i + 1,
                                         texto,
-                                        total_peso
+                                        total
                                     ));
                                 }
+
+                                // desglose de la ruta con paradas
+                                if !self.tramos.is_empty() {
+                                    ui.label("Por tramo:");
+                                    for (i, t) in self.tramos.iter().enumerate() {
+                                        let texto = t
+                                            .camino
+                                            .iter()
+                                            .map(|&idx| format!("{}({})", self.labels[idx], idx))
+                                            .collect::<Vec<_>>()
+                                            .join(" -> ");
+                                        ui.colored_label(
+                                            PALETA[i % PALETA.len()],
+                                            format!("Tramo {}: {}   |   costo = {}", i + 1, texto, t.costo),
+                                        );
+                                    }
+                                    if !self.ruta_exacta {
+                                        ui.small("Orden de paradas heurístico, puede no ser el óptimo.");
+                                    }
+                                }
+
+                                // el valor que da el solver: en los modos ancho/confiable/horario/giros la
+                                // suma de pesos no es lo que se optimiza
+                                if let Some(r) = &self.resumen {
+                                    ui.label(r);
+                                }
                             }
                         });
+
+                    // === Partición por origen ===
+                    if !self.fuente.is_empty() {
+                        egui::CollapsingHeader::new("Partición por origen")
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for (pos, &(o, _)) in self.origenes.iter().enumerate() {
+                                    // un origen repetido solo se lista la primera vez
+                                    if self.origenes[..pos].iter().any(|&(p, _)| p == o) {
+                                        continue;
+                                    }
+                                    // "nodo=costo" de cada nodo que atiende
+                                    let atendidos = self
+                                        .fuente
+                                        .iter()
+                                        .enumerate()
+                                        .filter_map(|(v, f)| match f {
+                                            Some((f, d)) if *f == o => {
+                                                Some(format!("{}={}", self.labels[v], d))
+                                            }
+                                            _ => None,
+                                        })
+                                        .collect::<Vec<_>>();
+                                    ui.colored_label(
+                                        PALETA[pos % PALETA.len()],
+                                        format!(
+                                            "{}: {} nodos",
+                                            self.nombre_nodo(o),
+                                            atendidos.len()
+                                        ),
+                                    );
+                                    ui.monospace(atendidos.join("  "));
+                                }
+                                let sin = self.fuente.iter().filter(|f| f.is_none()).count();
+                                if sin > 0 {
+                                    ui.label(format!("{} nodos sin origen que los alcance", sin));
+                                }
+                            });
+                    }
+
+                    // === Flujo ===
+                    if let Some(f) = &self.flujo {
+                        egui::CollapsingHeader::new("Flujo y corte mínimo")
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                match f.costo {
+                                    Some(c) => ui.label(format!("Flujo = {}, costo = {}", f.valor, c)),
+                                    None => ui.label(format!("Flujo = {}", f.valor)),
+                                };
+                                let g = self.grafo.as_ref().unwrap();
+                                ui.label(format!("Corte mínimo ({} aristas):", f.corte.len()));
+                                for &id in &f.corte {
+                                    let e = petgraph::graph::EdgeIndex::new(id);
+                                    let (u, v) = g.edge_endpoints(e).unwrap();
+                                    ui.monospace(format!(
+                                        "{} -> {}   (capacidad {})",
+                                        self.nombre_nodo(u.index()),
+                                        self.nombre_nodo(v.index()),
+                                        self.capacidades[id]
+                                    ));
+                                }
+                                let lado = (0..self.labels.len())
+                                    .filter(|&v| f.lado_origen[v])
+                                    .map(|v| self.labels[v].clone())
+                                    .collect::<Vec<_>>();
+                                ui.small(format!("Lado del origen: {}", lado.join(" ")));
+                            });
+                    }
+
+                    // === Tabla de distancias ===
+                    let mut celda = None;
+                    egui::CollapsingHeader::new("Tabla de distancias")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            let Some((dist, _)) = &self.tabla else {
+                                ui.label("Calcula la tabla desde el panel de controles.");
+                                return;
+                            };
+                            ui.small("Fila = desde, columna = hasta. Clic en una celda para ver el camino.");
+                            egui::ScrollArea::horizontal().show(ui, |ui| {
+                                egui::Grid::new("tabla_distancias")
+                                    .striped(true)
+                                    .show(ui, |ui| {
+                                        ui.label("");
+                                        for l in &self.labels {
+                                            ui.strong(l);
+                                        }
+                                        ui.end_row();
+                                        for (i, fila) in dist.iter().enumerate() {
+                                            ui.strong(&self.labels[i]);
+                                            for (j, d) in fila.iter().enumerate() {
+                                                let texto = match d {
+                                                    Some(d) => d.to_string(),
+                                                    None => "∞".into(),
+                                                };
+                                                let marcada = (i, j) == (self.origen, self.destino);
+                                                if ui.selectable_label(marcada, texto).clicked() {
+                                                    celda = Some((i, j));
+                                                }
+                                            }
+                                            ui.end_row();
+                                        }
+                                    });
+                            });
+                        });
+                    if let Some((i, j)) = celda {
+                        self.elegir_celda(i, j);
+                    }
                 });
         });
     }
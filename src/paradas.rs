@@ -0,0 +1,286 @@
+use crate::dijkstra::{dijkstra_eventos, iter_caminos};
+use crate::peso::Peso;
+use petgraph::graph::DiGraph;
+
+// Ruta de origen a destino pasando por paradas obligatorias. Se corre un Dijkstra desde el
+// origen y desde cada parada, y con esas distancias se arma el orden: tal cual viene, o el mejor
+// posible (programación dinámica sobre subconjuntos, Held-Karp) si son pocas paradas, o uno
+// bueno (vecino más cercano + 2-opt) si son muchas.
+
+/// Con hasta esta cantidad de paradas el orden libre se resuelve exacto, O(2^k · k²).
+pub const MAX_PARADAS_EXACTO: usize = 12;
+
+/// Un tramo de la ruta, de una parada a la siguiente.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tramo<W> {
+    pub desde: usize,
+    pub hasta: usize,
+    /// Camino mínimo del tramo, de `desde` a `hasta`.
+    pub camino: Vec<usize>,
+    pub costo: W,
+}
+
+/// Resultado de [`ruta_con_paradas`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RutaConParadas<W> {
+    /// La ruta completa, puede repetir nodos si dos tramos comparten parte del camino.
+    pub camino: Vec<usize>,
+    /// Los tramos en el orden en que se recorren.
+    pub tramos: Vec<Tramo<W>>,
+    pub total: W,
+    /// `false` si el orden salió de la heurística (más de [`MAX_PARADAS_EXACTO`] paradas en
+    /// orden libre) y podría no ser el óptimo.
+    pub exacta: bool,
+}
+
+/// Ruta más barata de `origen` a `destino` que pasa por todas las `paradas`, en ese orden si
+/// `en_orden` o en el orden que convenga si no.
+///
+/// Devuelve `Ok(None)` si algún tramo no es alcanzable (en orden libre, si ningún orden sirve;
+/// con la heurística, si no encontró ninguno que sirva).
+/// Igual que [`dijkstra_detallado`](crate::dijkstra::dijkstra_detallado) devuelve `Err` con pesos
+/// negativos o si el costo se desborda.
+pub fn ruta_con_paradas<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+    destino: usize,
+    paradas: &[usize],
+    en_orden: bool,
+) -> Result<Option<RutaConParadas<W>>, String> {
+    // puntos[0] = origen, luego las paradas; el destino no necesita su propio Dijkstra
+    let mut puntos = vec![origen];
+    puntos.extend_from_slice(paradas);
+    let mut dist = vec![];
+    let mut preds = vec![];
+    for &p in &puntos {
+        let (d, pr, _) = dijkstra_eventos(g, p)?;
+        dist.push(d);
+        preds.push(pr);
+    }
+    // costo entre puntos i -> j, con j = puntos.len() el destino
+    let nodo = |j: usize| {
+        if j == puntos.len() {
+            destino
+        } else {
+            puntos[j]
+        }
+    };
+    let costo = |i: usize, j: usize| dist[i][nodo(j)];
+
+    let k = paradas.len();
+    let fin = puntos.len();
+    let (orden, exacta) = if en_orden || k <= 1 {
+        ((1..=k).collect(), true)
+    } else if k <= MAX_PARADAS_EXACTO {
+        match orden_exacto(k, fin, &costo)? {
+            Some(o) => (o, true),
+            None => return Ok(None),
+        }
+    } else {
+        match orden_heuristico(k, fin, &costo)? {
+            Some(o) => (o, false),
+            None => return Ok(None),
+        }
+    };
+
+    // origen, paradas en el orden elegido, destino
+    let mut secuencia = vec![0];
+    secuencia.extend(orden);
+    secuencia.push(fin);
+
+    let mut tramos = vec![];
+    let mut total = W::cero();
+    let mut camino = vec![origen];
+    for par in secuencia.windows(2) {
+        let (i, j) = (par[0], par[1]);
+        let Some(c) = costo(i, j) else {
+            return Ok(None);
+        };
+        total = total.sumar(c).ok_or_else(desborde)?;
+        let tramo = iter_caminos(&preds[i], puntos[i], nodo(j))
+            .next()
+            .expect("alcanzable, hay camino");
+        camino.extend_from_slice(&tramo[1..]);
+        tramos.push(Tramo {
+            desde: puntos[i],
+            hasta: nodo(j),
+            camino: tramo,
+            costo: c,
+        });
+    }
+    Ok(Some(RutaConParadas {
+        camino,
+        tramos,
+        total,
+        exacta,
+    }))
+}
+
+fn desborde() -> String {
+    "Desborde: el costo de la ruta no cabe en el tipo de peso".into()
+}
+
+// Held-Karp: mejor[mascara][j] = costo mínimo saliendo del origen, visitando las paradas de
+// `mascara` y terminando en la parada j (índices 1..=k de los puntos, bit j-1 de la máscara)
+fn orden_exacto<W: Peso>(
+    k: usize,
+    fin: usize,
+    costo: &dyn Fn(usize, usize) -> Option<W>,
+) -> Result<Option<Vec<usize>>, String> {
+    let completo = (1usize << k) - 1;
+    let mut mejor: Vec<Vec<Option<W>>> = vec![vec![None; k + 1]; 1 << k];
+    let mut previo: Vec<Vec<usize>> = vec![vec![0; k + 1]; 1 << k];
+    for j in 1..=k {
+        mejor[1 << (j - 1)][j] = costo(0, j);
+    }
+    for mascara in 1..=completo {
+        for j in 1..=k {
+            let Some(c) = mejor[mascara][j] else {
+                continue;
+            };
+            for s in 1..=k {
+                if mascara & (1 << (s - 1)) != 0 {
+                    continue;
+                }
+                let Some(w) = costo(j, s) else {
+                    continue;
+                };
+                let alt = c.sumar(w).ok_or_else(desborde)?;
+                let m = mascara | (1 << (s - 1));
+                if mejor[m][s].is_none_or(|b| alt < b) {
+                    mejor[m][s] = Some(alt);
+                    previo[m][s] = j;
+                }
+            }
+        }
+    }
+
+    let mut ultimo: Option<(W, usize)> = None;
+    for (j, &c) in mejor[completo].iter().enumerate().skip(1) {
+        if let (Some(c), Some(w)) = (c, costo(j, fin)) {
+            let alt = c.sumar(w).ok_or_else(desborde)?;
+            if ultimo.is_none_or(|(b, _)| alt < b) {
+                ultimo = Some((alt, j));
+            }
+        }
+    }
+    let Some((_, mut j)) = ultimo else {
+        return Ok(None);
+    };
+    let mut orden = vec![];
+    let mut mascara = completo;
+    while j != 0 {
+        orden.push(j);
+        let p = previo[mascara][j];
+        mascara &= !(1 << (j - 1));
+        j = p;
+    }
+    orden.reverse();
+    Ok(Some(orden))
+}
+
+// Vecino más cercano y después 2-opt (invertir tramos mientras baje el costo)
+fn orden_heuristico<W: Peso>(
+    k: usize,
+    fin: usize,
+    costo: &dyn Fn(usize, usize) -> Option<W>,
+) -> Result<Option<Vec<usize>>, String> {
+    let mut orden = vec![];
+    let mut pendientes: Vec<usize> = (1..=k).collect();
+    let mut actual = 0;
+    while !pendientes.is_empty() {
+        let Some(pos) = (0..pendientes.len())
+            .filter(|&p| costo(actual, pendientes[p]).is_some())
+            .min_by_key(|&p| costo(actual, pendientes[p]))
+        else {
+            // sin salida desde aquí, el resto va como venga y que 2-opt intente arreglarlo
+            orden.append(&mut pendientes);
+            break;
+        };
+        actual = pendientes.remove(pos);
+        orden.push(actual);
+    }
+
+    let total = |orden: &[usize]| -> Result<Option<W>, String> {
+        let mut t = W::cero();
+        let mut previo = 0;
+        for &j in orden.iter().chain(std::iter::once(&fin)) {
+            let Some(c) = costo(previo, j) else {
+                return Ok(None);
+            };
+            t = t.sumar(c).ok_or_else(desborde)?;
+            previo = j;
+        }
+        Ok(Some(t))
+    };
+    let mut actual_total = total(&orden)?;
+    let mut mejoro = true;
+    while mejoro {
+        mejoro = false;
+        for i in 0..k {
+            for j in i + 1..k {
+                let mut otro = orden.clone();
+                otro[i..=j].reverse();
+                let t = total(&otro)?;
+                if t.is_some() && actual_total.is_none_or(|a| t < Some(a)) {
+                    orden = otro;
+                    actual_total = t;
+                    mejoro = true;
+                }
+            }
+        }
+    }
+    Ok(actual_total.map(|_| orden))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grafo::de_texto;
+
+    // O - Y - X - T en línea, ida y vuelta con peso 1
+    const LINEA: &str = "O Y 1\nY O 1\nY X 1\nX Y 1\nX T 1\nT X 1";
+
+    #[test]
+    fn en_orden_respeta_el_orden_dado() {
+        let g = de_texto::<i64>(LINEA);
+        let r = ruta_con_paradas(&g, 0, 3, &[2, 1], true).unwrap().unwrap();
+        assert_eq!(r.total, 5);
+        assert_eq!(r.camino, vec![0, 1, 2, 1, 2, 3]);
+        assert_eq!(r.tramos.len(), 3);
+        assert_eq!((r.tramos[1].desde, r.tramos[1].hasta), (2, 1));
+        assert!(r.exacta);
+    }
+
+    #[test]
+    fn orden_libre_busca_el_mejor() {
+        let g = de_texto::<i64>(LINEA);
+        let r = ruta_con_paradas(&g, 0, 3, &[2, 1], false).unwrap().unwrap();
+        assert_eq!(r.total, 3);
+        assert_eq!(r.camino, vec![0, 1, 2, 3]);
+        assert!(r.exacta);
+    }
+
+    #[test]
+    fn muchas_paradas_usan_la_heuristica() {
+        // 0 - 1 - ... - 14 en línea, las paradas 1..=13 desordenadas
+        let texto: String = (0..14)
+            .map(|i| format!("n{} n{} 1\nn{} n{} 1\n", i, i + 1, i + 1, i))
+            .collect();
+        let g = de_texto::<i64>(&texto);
+        let paradas = [7, 3, 12, 1, 9, 5, 13, 2, 11, 4, 8, 6, 10];
+        let r = ruta_con_paradas(&g, 0, 14, &paradas, false)
+            .unwrap()
+            .unwrap();
+        assert!(!r.exacta);
+        assert_eq!(r.total, 14);
+        assert_eq!(r.camino, (0..=14).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn parada_inalcanzable() {
+        let g = de_texto::<i64>("nodos: O A X T\nO A 1\nA T 1");
+        assert_eq!(ruta_con_paradas(&g, 0, 3, &[2], true).unwrap(), None);
+        assert_eq!(ruta_con_paradas(&g, 0, 3, &[2, 1], false).unwrap(), None);
+    }
+}
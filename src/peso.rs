@@ -0,0 +1,175 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+// Tipos de peso que aceptan los solvers. Antes todo era i32 con saturating_add, que se quedaba
+// pegado en i32::MAX sin avisar; ahora la suma es "checked" y el solver reporta el desborde.
+
+/// Peso de arista que entiende el solver: orden total, un cero y una suma que avisa si se desborda.
+///
+/// Implementado para todos los enteros primitivos (`i128`/`u128` sirven como opción de enteros
+/// grandes, siguen siendo "checked") y para [`Real`], un `f64` con orden total.
+pub trait Peso: Copy + Ord + fmt::Debug + fmt::Display {
+    /// Elemento neutro de la suma, la distancia del origen a sí mismo.
+    fn cero() -> Self;
+    /// `self + otro`, `None` si el resultado no se puede representar.
+    fn sumar(self, otro: Self) -> Option<Self>;
+    /// `self - otro`, `None` si el resultado no se puede representar. Lo usa Johnson para
+    /// repesar las aristas con los potenciales.
+    fn restar(self, otro: Self) -> Option<Self>;
+    /// `self * otro`, `None` si el resultado no se puede representar. Lo usa el flujo de costo
+    /// mínimo para el costo total (costo por unidad por unidades).
+    fn multiplicar(self, otro: Self) -> Option<Self>;
+    /// Convierte una cota real al tipo de peso sin pasarse (piso para enteros), la usan las
+    /// heurísticas de A* para seguir siendo admisibles.
+    fn desde_f64(x: f64) -> Self;
+    /// El peso como real (puede perder precisión con enteros enormes), lo usa el Dijkstra con
+    /// horarios para las aristas que no tienen función de tiempo.
+    fn a_f64(self) -> f64;
+}
+
+macro_rules! peso_entero {
+    ($($t:ty),*) => {
+        $(
+            impl Peso for $t {
+                fn cero() -> Self {
+                    0
+                }
+                fn sumar(self, otro: Self) -> Option<Self> {
+                    self.checked_add(otro)
+                }
+                fn restar(self, otro: Self) -> Option<Self> {
+                    self.checked_sub(otro)
+                }
+                fn multiplicar(self, otro: Self) -> Option<Self> {
+                    self.checked_mul(otro)
+                }
+                fn desde_f64(x: f64) -> Self {
+                    // `as` satura en los extremos y NaN da 0
+                    x.floor() as $t
+                }
+                fn a_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+peso_entero!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+/// `f64` con orden total (`f64::total_cmp`) para poder usarlo como peso en el `BinaryHeap`.
+///
+/// No acepta NaN al parsear, y una suma que da infinito o NaN cuenta como desborde.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Real(pub f64);
+
+impl PartialEq for Real {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Real {}
+
+impl Ord for Real {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+impl PartialOrd for Real {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Real {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for Real {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let x: f64 = s.parse().map_err(|_| format!("'{}' no es un número", s))?;
+        if !x.is_finite() {
+            return Err(format!("'{}' no es un número finito", s));
+        }
+        Ok(Real(x))
+    }
+}
+
+impl Peso for Real {
+    fn cero() -> Self {
+        Real(0.0)
+    }
+    fn sumar(self, otro: Self) -> Option<Self> {
+        let r = self.0 + otro.0;
+        r.is_finite().then_some(Real(r))
+    }
+    fn restar(self, otro: Self) -> Option<Self> {
+        let r = self.0 - otro.0;
+        r.is_finite().then_some(Real(r))
+    }
+    fn multiplicar(self, otro: Self) -> Option<Self> {
+        let r = self.0 * otro.0;
+        r.is_finite().then_some(Real(r))
+    }
+    fn desde_f64(x: f64) -> Self {
+        Real(x)
+    }
+    fn a_f64(self) -> f64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::dijkstra_detallado;
+    use crate::grafo::de_texto;
+
+    #[test]
+    fn las_sumas_avisan_el_desborde() {
+        assert_eq!(i32::MAX.sumar(1), None);
+        assert_eq!(0u64.restar(1), None);
+        assert_eq!(Real(f64::MAX).sumar(Real(f64::MAX)), None);
+        assert_eq!(Real(1.5).sumar(Real(2.0)), Some(Real(3.5)));
+    }
+
+    #[test]
+    fn desde_f64_no_se_pasa() {
+        assert_eq!(i64::desde_f64(2.9), 2);
+        assert_eq!(i64::desde_f64(-0.5), -1);
+        assert_eq!(u8::desde_f64(1e9), u8::MAX);
+    }
+
+    #[test]
+    fn real_no_acepta_nan_ni_infinito() {
+        assert!("NaN".parse::<Real>().is_err());
+        assert!("inf".parse::<Real>().is_err());
+        assert_eq!("0.25".parse::<Real>(), Ok(Real(0.25)));
+        assert!(Real(-0.0) < Real(0.0));
+    }
+
+    #[test]
+    fn dijkstra_con_otros_tipos_de_peso() {
+        let g = de_texto::<Real>("A B 0.5\nB C 0.25\nA C 1");
+        let (dist, _, _) = dijkstra_detallado(&g, 0).unwrap();
+        assert_eq!(dist[2], Some(Real(0.75)));
+
+        let g = de_texto::<u64>("A B 4000000000\nB C 4000000000");
+        let (dist, _, _) = dijkstra_detallado(&g, 0).unwrap();
+        assert_eq!(dist[2], Some(8_000_000_000));
+
+        let g = de_texto::<i32>("A B 2000000000\nB C 2000000000");
+        assert!(
+            dijkstra_detallado(&g, 0)
+                .unwrap_err()
+                .starts_with("Desborde")
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::peso::Peso;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// Camino más barato con un recurso limitado (tiempo, combustible, peaje...). Ya no alcanza con
+// una distancia por nodo: un camino caro pero que gasta poco recurso puede ser el único que
+// llega. Cada nodo guarda etiquetas (costo, recurso) de Pareto, ninguna mejor que otra en las dos
+// cosas, y se van fijando por costo como en Dijkstra ("label-setting"). Con presupuesto la
+// primera etiqueta que se fija en el destino es la respuesta; sin él, las que se fijan ahí forman
+// el frente de Pareto entre los dos criterios.
+
+/// Camino de costo mínimo de `origen` a `destino` cuyo recurso total no pasa de `presupuesto`.
+/// `recursos[e]` es el recurso de la arista con índice `e` (lo que devuelve
+/// [`GrafoManual::generar_con_recursos`](crate::grafo::GrafoManual::generar_con_recursos)).
+///
+/// Devuelve `(camino, costo, recurso)`, entre los de igual costo el que gasta menos recurso, o
+/// `None` si ningún camino entra en el presupuesto. Devuelve `Err` si hay costos o recursos
+/// negativos, si falta el recurso de alguna arista o si un costo se desborda (sin presupuesto que
+/// lo corte, en [`frente_pareto`], también si se desborda un recurso).
+///
+/// En el peor caso la cantidad de etiquetas crece mucho (el problema es NP-difícil), pero con
+/// recursos acotados o poco variados se mantiene chica.
+pub fn camino_con_presupuesto<W: Peso, R: Peso>(
+    g: &DiGraph<String, W>,
+    recursos: &[R],
+    origen: usize,
+    destino: usize,
+    presupuesto: R,
+) -> Result<Option<(Vec<usize>, W, R)>, String> {
+    if presupuesto < R::cero() {
+        validar(g, recursos)?;
+        return Ok(None);
+    }
+    let mut r = etiquetar(g, recursos, origen, destino, Some(presupuesto))?;
+    Ok(r.pop())
+}
+
+/// Frente de Pareto entre `origen` y `destino` con dos criterios, el peso de cada arista y su
+/// recurso `recursos[e]` (por ejemplo distancia y riesgo): todos los caminos que no tienen otro
+/// que sea igual o mejor en los dos y estrictamente mejor en alguno.
+///
+/// Devuelve un `(camino, costo, recurso)` por punto del frente, de menor a mayor costo (y por lo
+/// tanto de mayor a menor recurso). Si dos caminos dan el mismo punto queda uno solo. Vacío si
+/// `destino` no es alcanzable. Los errores son los mismos que en [`camino_con_presupuesto`].
+pub fn frente_pareto<W: Peso, R: Peso>(
+    g: &DiGraph<String, W>,
+    recursos: &[R],
+    origen: usize,
+    destino: usize,
+) -> Result<Vec<(Vec<usize>, W, R)>, String> {
+    etiquetar(g, recursos, origen, destino, None)
+}
+
+fn validar<W: Peso, R: Peso>(g: &DiGraph<String, W>, recursos: &[R]) -> Result<(), String> {
+    if recursos.len() != g.edge_count() {
+        return Err(format!(
+            "Hay {} aristas pero {} recursos",
+            g.edge_count(),
+            recursos.len()
+        ));
+    }
+    for e in g.edge_references() {
+        let (w, r) = (*e.weight(), recursos[e.id().index()]);
+        if w < W::cero() || r < R::cero() {
+            return Err(format!(
+                "Arista {} -> {} con costo {} y recurso {}, no se admiten negativos",
+                e.source().index(),
+                e.target().index(),
+                w,
+                r
+            ));
+        }
+    }
+    Ok(())
+}
+
+// El label-setting de los dos. Con presupuesto para en la primera etiqueta que fija el destino
+// (la más barata que entra); sin presupuesto sigue hasta vaciar el heap y cada etiqueta fijada en
+// el destino es un punto del frente, porque salen por costo y solo se fija si gasta menos
+// recurso que todas las anteriores
+fn etiquetar<W: Peso, R: Peso>(
+    g: &DiGraph<String, W>,
+    recursos: &[R],
+    origen: usize,
+    destino: usize,
+    presupuesto: Option<R>,
+) -> Result<Vec<(Vec<usize>, W, R)>, String> {
+    validar(g, recursos)?;
+
+    // etiquetas[i] = (nodo, costo, recurso, etiqueta previa); el heap guarda índices a esto
+    let mut etiquetas: Vec<(usize, W, R, Option<usize>)> =
+        vec![(origen, W::cero(), R::cero(), None)];
+    // las etiquetas ya fijadas de cada nodo, forman su frente de Pareto
+    let mut fijadas: Vec<Vec<(W, R)>> = vec![vec![]; g.node_count()];
+    let mut heap: BinaryHeap<Reverse<(W, R, usize)>> = BinaryHeap::new();
+    heap.push(Reverse((W::cero(), R::cero(), 0)));
+    let mut frente = vec![];
+
+    while let Some(Reverse((costo, recurso, i))) = heap.pop() {
+        let u = etiquetas[i].0;
+        if dominada(&fijadas[u], costo, recurso) {
+            continue;
+        }
+        fijadas[u].push((costo, recurso));
+        if u == destino {
+            let mut camino = vec![];
+            let mut x = Some(i);
+            while let Some(j) = x {
+                camino.push(etiquetas[j].0);
+                x = etiquetas[j].3;
+            }
+            camino.reverse();
+            frente.push((camino, costo, recurso));
+            if presupuesto.is_some() {
+                break;
+            }
+            continue;
+        }
+
+        for e in g.edges(NodeIndex::new(u)) {
+            let v = e.target().index();
+            let r = recursos[e.id().index()];
+            // si la suma se desborda seguro pasa del presupuesto
+            let Some(nuevo_r) = recurso
+                .sumar(r)
+                .filter(|&x| presupuesto.is_none_or(|p| x <= p))
+            else {
+                if presupuesto.is_none() {
+                    return Err(format!(
+                        "Desborde: recurso {} + {} no cabe en el tipo de recurso",
+                        recurso, r
+                    ));
+                }
+                continue;
+            };
+            let w = *e.weight();
+            let nuevo_c = costo.sumar(w).ok_or_else(|| {
+                format!(
+                    "Desborde: costo {} + {} no cabe en el tipo de peso",
+                    costo, w
+                )
+            })?;
+            // si ya hay un punto del frente mejor, extenderla no sirve (no hay negativos)
+            if dominada(&fijadas[v], nuevo_c, nuevo_r)
+                || dominada(&fijadas[destino], nuevo_c, nuevo_r)
+            {
+                continue;
+            }
+            etiquetas.push((v, nuevo_c, nuevo_r, Some(i)));
+            heap.push(Reverse((nuevo_c, nuevo_r, etiquetas.len() - 1)));
+        }
+    }
+    Ok(frente)
+}
+
+// Alguna etiqueta fijada cuesta y gasta lo mismo o menos
+fn dominada<W: Peso, R: Peso>(frente: &[(W, R)], costo: W, recurso: R) -> bool {
+    frente.iter().any(|&(c, r)| c <= costo && r <= recurso)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grafo::GrafoManual;
+
+    // O=0 T=1 A=2 B=3 C=4; "costo recurso" por arista. Directo: (10, 1), por A: (2, 10), por B:
+    // (8, 4), por C: (10, 10) que no le gana a nadie, y la paralela O -> T: (12, 0)
+    const RUTAS: &str = "O T 10 1\nO A 1 5\nA T 1 5\nO B 4 2\nB T 4 2\nO C 5 5\nC T 5 5\nO T 12 0";
+
+    fn leer(texto: &str) -> (DiGraph<String, i64>, Vec<i64>) {
+        GrafoManual::new(vec![], texto.into())
+            .generar_con_recursos()
+            .unwrap()
+    }
+
+    #[test]
+    fn el_presupuesto_elige_el_camino() {
+        let (g, r) = leer(RUTAS);
+        let con = |p| camino_con_presupuesto(&g, &r, 0, 1, p).unwrap();
+        assert_eq!(con(10), Some((vec![0, 2, 1], 2, 10)));
+        assert_eq!(con(9), Some((vec![0, 3, 1], 8, 4)));
+        assert_eq!(con(3), Some((vec![0, 1], 10, 1)));
+        assert_eq!(con(0), Some((vec![0, 1], 12, 0)));
+        assert_eq!(con(-1), None);
+    }
+
+    #[test]
+    fn frente_sin_dominados() {
+        let (g, r) = leer(RUTAS);
+        assert_eq!(
+            frente_pareto(&g, &r, 0, 1).unwrap(),
+            vec![
+                (vec![0, 2, 1], 2, 10),
+                (vec![0, 3, 1], 8, 4),
+                (vec![0, 1], 10, 1),
+                (vec![0, 1], 12, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn frente_con_puntos_repetidos_deja_uno() {
+        let (g, r) = leer("nodos: O T A B\nO A 1 1\nA T 1 1\nO B 1 1\nB T 1 1");
+        let frente = frente_pareto(&g, &r, 0, 1).unwrap();
+        assert_eq!(frente.len(), 1);
+        assert_eq!((frente[0].1, frente[0].2), (2, 2));
+        let (g, r) = leer("nodos: O T\nO A 1 1");
+        assert!(frente_pareto(&g, &r, 0, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn sin_camino_o_con_negativos() {
+        let (g, r) = leer("nodos: O T\nO A 1 1");
+        assert_eq!(camino_con_presupuesto(&g, &r, 0, 1, 5).unwrap(), None);
+        let (g, r) = leer("O T 1 -1");
+        assert!(camino_con_presupuesto(&g, &r, 0, 1, 5).is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use crate::dijkstra::{Entrada, Resultado, es_ancestro};
+use crate::peso::Real;
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+
+// Otros "semianillos" de caminos con la misma estructura de Dijkstra: en vez de sumar pesos y
+// quedarse con el menor, se combinan de otra forma y se queda el mejor según otro orden.
+// - Camino más ancho (cuello de botella): el valor de un camino es su arista más chica y se busca
+//   el mayor, por ejemplo la capacidad de una red.
+// - Camino más confiable: el valor es el producto de las probabilidades y se busca el mayor.
+// Funciona mientras extender un camino nunca lo mejore (el mínimo solo baja, el producto de
+// probabilidades ≤ 1 también), igual que los pesos no negativos en Dijkstra. Pero a diferencia de
+// Dijkstra con pesos positivos, extender puede dejarlo igual (una arista más ancha que el cuello de
+// botella, una probabilidad 1), así que un nodo puede fijarse antes que alguno de sus predecesores
+// empatados: esos empates se agregan igual al llegar, salvo que armen un ciclo en preds.
+
+/// Capacidad de un camino, el peso de su arista más chica. El origen tiene capacidad infinita
+/// (el camino vacío no tiene cuello de botella).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Capacidad<W> {
+    Finita(W),
+    Infinita,
+}
+
+impl<W: fmt::Display> fmt::Display for Capacidad<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Capacidad::Finita(w) => write!(f, "{}", w),
+            Capacidad::Infinita => write!(f, "∞"),
+        }
+    }
+}
+
+/// Camino más ancho desde `origen`: para cada nodo la mayor capacidad (peso mínimo del camino)
+/// con la que se puede llegar.
+///
+/// Devuelve `(cap, preds, log)` como [`dijkstra_detallado`](crate::dijkstra::dijkstra_detallado):
+/// `cap[v]` es `None` si `v` no es alcanzable, `preds[v]` los predecesores que empatan en la mejor
+/// capacidad (sirve con [`reconstruir_todos_caminos`](crate::dijkstra::reconstruir_todos_caminos))
+/// y el log usa `cap` en vez de `dist`. Acepta cualquier peso, también negativos.
+pub fn camino_mas_ancho<W: Ord + Copy + fmt::Display>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+) -> Resultado<Capacidad<W>> {
+    let (cap, preds, log) = mejor_camino(
+        g,
+        origen,
+        Reverse(Capacidad::Infinita),
+        &|Reverse(c), w| Reverse(c.min(Capacidad::Finita(w))),
+        "cap",
+    );
+    let cap = cap.into_iter().map(|c| c.map(|Reverse(c)| c)).collect();
+    (cap, preds, log)
+}
+
+/// Camino más confiable desde `origen`: cada arista tiene la probabilidad de que funcione (entre
+/// 0 y 1) y se busca el camino con el mayor producto.
+///
+/// Devuelve `(prob, preds, log)` igual que [`camino_mas_ancho`], con `prob[origen] = 1`. Devuelve
+/// `Err` si alguna probabilidad no está entre 0 y 1.
+pub fn camino_mas_confiable(
+    g: &DiGraph<String, Real>,
+    origen: usize,
+) -> Result<Resultado<Real>, String> {
+    for e in g.edge_references() {
+        let p = e.weight().0;
+        if !(0.0..=1.0).contains(&p) {
+            return Err(format!(
+                "Probabilidad fuera de [0, 1] en la arista {} -> {} (p={})",
+                e.source().index(),
+                e.target().index(),
+                p
+            ));
+        }
+    }
+    let (prob, preds, log) = mejor_camino(
+        g,
+        origen,
+        Reverse(Real(1.0)),
+        &|Reverse(p), w| Reverse(Real(p.0 * w.0)),
+        "prob",
+    );
+    let prob = prob.into_iter().map(|p| p.map(|Reverse(p)| p)).collect();
+    Ok((prob, preds, log))
+}
+
+// El Dijkstra de siempre con otra forma de extender: el valor `K` es mejor mientras más chico
+// (por eso el Reverse, así se reusa Entrada tal cual) y `nombre` es cómo se llama en el log
+fn mejor_camino<W: Copy + fmt::Display, K: Ord + Copy + fmt::Display>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+    inicial: Reverse<K>,
+    extender: &dyn Fn(Reverse<K>, W) -> Reverse<K>,
+    nombre: &str,
+) -> Resultado<Reverse<K>> {
+    let n = g.node_count();
+    let mut valor: Vec<Option<Reverse<K>>> = vec![None; n];
+    let mut preds: Vec<Vec<usize>> = vec![vec![]; n];
+    let mut visitado = vec![false; n];
+    let mut heap: BinaryHeap<Entrada<Reverse<K>>> = BinaryHeap::new();
+    let mut log = vec![];
+
+    valor[origen] = Some(inicial);
+    heap.push(Entrada {
+        dist: inicial,
+        node: origen,
+    });
+    log.push(format!(
+        "Inicializo {}[{}]={}, resto sin camino",
+        nombre, origen, inicial.0
+    ));
+
+    while let Some(Entrada { dist: k_u, node: u }) = heap.pop() {
+        if visitado[u] {
+            log.push(format!(
+                "  Descarto u={} con {}={} (ya visitado)",
+                u, nombre, k_u.0
+            ));
+            continue;
+        }
+        visitado[u] = true;
+        log.push(format!("Selecciono u={} con {}={}", u, nombre, k_u.0));
+
+        for e in g.edges(petgraph::prelude::NodeIndex::new(u)) {
+            let v = e.target().index();
+            let w = *e.weight();
+            let alt = extender(k_u, w);
+            if visitado[v] {
+                if valor[v] == Some(alt) && !preds[v].contains(&u) && !es_ancestro(&preds, v, u) {
+                    preds[v].push(u);
+                    log.push(format!("  Empate óptimo hacia {}: también via {}", v, u));
+                }
+                continue;
+            }
+            match valor[v] {
+                None => {
+                    valor[v] = Some(alt);
+                    preds[v] = vec![u];
+                    heap.push(Entrada { dist: alt, node: v });
+                    log.push(format!(
+                        "  Trazo ({} -> {}, w={}): {}[{}]={}",
+                        u, v, w, nombre, v, alt.0
+                    ));
+                }
+                Some(curr) if alt < curr => {
+                    valor[v] = Some(alt);
+                    preds[v] = vec![u];
+                    heap.push(Entrada { dist: alt, node: v });
+                    log.push(format!(
+                        "  Mejora ({} -> {}, w={}): {}[{}] {}→{}",
+                        u, v, w, nombre, v, curr.0, alt.0
+                    ));
+                }
+                Some(curr) if alt == curr && !preds[v].contains(&u) => {
+                    preds[v].push(u);
+                    log.push(format!("  Empate óptimo hacia {}: también via {}", v, u));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    log.push(format!(
+        "Fin: {} nodos alcanzados",
+        valor.iter().filter(|d| d.is_some()).count()
+    ));
+    (valor, preds, log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::contar_caminos;
+    use crate::grafo::de_texto;
+
+    // con empates la cola saca primero el índice mayor: b fija el valor de c y c sale antes que a,
+    // así el empate por a llega cuando c ya está fijo
+    const DIAMANTE: &str = "nodos: O A C B\nO A 5\nO B 5\nA C 9\nB C 9";
+
+    #[test]
+    fn ancho_guarda_empates_hacia_nodos_ya_fijos() {
+        let g = de_texto::<i64>(DIAMANTE);
+        let (cap, preds, _) = camino_mas_ancho(&g, 0);
+        assert_eq!(cap[2], Some(Capacidad::Finita(5)));
+        let mut p = preds[2].clone();
+        p.sort();
+        assert_eq!(p, vec![1, 3]);
+        assert_eq!(contar_caminos(&preds, 0, 2), Some(2));
+    }
+
+    #[test]
+    fn confiable_guarda_empates_con_probabilidad_uno() {
+        let g = de_texto::<Real>("nodos: O A C B\nO A 0.5\nO B 0.5\nA C 1\nB C 1");
+        let (prob, preds, _) = camino_mas_confiable(&g, 0).unwrap();
+        assert_eq!(prob[2], Some(Real(0.5)));
+        assert_eq!(contar_caminos(&preds, 0, 2), Some(2));
+    }
+
+    #[test]
+    fn ancho_prefiere_el_cuello_mas_grande() {
+        // directo por una arista angosta o dando la vuelta por dos anchas; D queda aislado
+        let g = de_texto::<i64>("nodos: O A T D\nO T 2\nO A 7\nA T 5\nO T 3");
+        let (cap, preds, _) = camino_mas_ancho(&g, 0);
+        assert_eq!(cap[0], Some(Capacidad::Infinita));
+        assert_eq!(cap[2], Some(Capacidad::Finita(5)));
+        assert_eq!(preds[2], vec![1]);
+        assert_eq!(cap[3], None);
+    }
+
+    #[test]
+    fn confiable_rechaza_probabilidades_fuera_de_rango() {
+        let g = de_texto::<Real>("A B 1.5");
+        assert!(camino_mas_confiable(&g, 0).is_err());
+        let g = de_texto::<Real>("A B 0.9\nB C 0.9\nA C 0.8");
+        let (prob, preds, _) = camino_mas_confiable(&g, 0).unwrap();
+        assert_eq!(prob[2], Some(Real(0.81)));
+        assert_eq!(preds[2], vec![1]);
+    }
+
+    #[test]
+    fn los_empates_no_arman_ciclos() {
+        // A <-> B con probabilidad 1: cada uno empata con el otro, solo uno puede quedar
+        let g = de_texto::<Real>("O A 1\nA B 1\nB A 1\nO B 1");
+        let (_, preds, _) = camino_mas_confiable(&g, 0).unwrap();
+        assert_eq!(contar_caminos(&preds, 0, 1), Some(2));
+        assert_eq!(contar_caminos(&preds, 0, 2), Some(1));
+        assert!(preds[0].is_empty());
+    }
+}
@@ -0,0 +1,244 @@
+use crate::bellman_ford::{FalloBellmanFord, bellman_ford_detallado};
+use crate::dijkstra::dijkstra_eventos;
+use crate::peso::Peso;
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+
+// Caminos mínimos entre todos los pares. Floyd-Warshall es O(V³) pero muy simple, conviene en
+// grafos chicos o densos; Johnson es Bellman-Ford una vez + Dijkstra desde cada nodo,
+// O(V·E log V), y gana en grafos dispersos. Los dos aceptan pesos negativos.
+
+/// Lo que devuelven [`floyd_warshall`] y [`johnson`]: `(dist, siguiente)`, con `dist[u][v]` la
+/// distancia de `u` a `v` y `siguiente[u][v]` el primer salto del camino.
+pub type TablaPares<W> = (Vec<Vec<Option<W>>>, Vec<Vec<Option<usize>>>);
+
+/// Floyd-Warshall sobre todo el grafo.
+///
+/// Devuelve `(dist, siguiente)`: `dist[i][j]` es la distancia mínima de `i` a `j` (`None` si no
+/// hay camino) y `siguiente[i][j]` el nodo que sigue a `i` en un camino mínimo hacia `j`
+/// (`siguiente[i][i] = i`), para reconstruirlo con [`camino_siguiente`]. Si hay un ciclo negativo
+/// se devuelve el ciclo igual que en [`bellman_ford_detallado`].
+pub fn floyd_warshall<W: Peso>(g: &DiGraph<String, W>) -> Result<TablaPares<W>, FalloBellmanFord> {
+    let n = g.node_count();
+    let mut dist: Vec<Vec<Option<W>>> = vec![vec![None; n]; n];
+    let mut siguiente: Vec<Vec<Option<usize>>> = vec![vec![None; n]; n];
+
+    for i in 0..n {
+        dist[i][i] = Some(W::cero());
+        siguiente[i][i] = Some(i);
+    }
+    for e in g.edge_references() {
+        let u = e.source().index();
+        let v = e.target().index();
+        let w = *e.weight();
+        if u == v && w >= W::cero() {
+            continue;
+        }
+        // con aristas paralelas se queda la más barata
+        if dist[u][v].is_none_or(|d| w < d) {
+            dist[u][v] = Some(w);
+            siguiente[u][v] = Some(v);
+        }
+    }
+
+    for k in 0..n {
+        for i in 0..n {
+            let Some(d_ik) = dist[i][k] else {
+                continue;
+            };
+            for j in 0..n {
+                let Some(d_kj) = dist[k][j] else {
+                    continue;
+                };
+                let alt = d_ik.sumar(d_kj).ok_or_else(|| {
+                    FalloBellmanFord::Desborde(format!(
+                        "Desborde: dist[{}][{}]={} + dist[{}][{}]={} no cabe en el tipo de peso",
+                        i, k, d_ik, k, j, d_kj
+                    ))
+                })?;
+                if dist[i][j].is_none_or(|d| alt < d) {
+                    dist[i][j] = Some(alt);
+                    siguiente[i][j] = siguiente[i][k];
+                }
+            }
+            // i está en un ciclo negativo, se corta antes de que las distancias se desborden
+            if dist[i][i].is_some_and(|d| d < W::cero()) {
+                return Err(ciclo_desde(g, i));
+            }
+        }
+    }
+    Ok((dist, siguiente))
+}
+
+/// Johnson: Bellman-Ford desde un nodo virtual para sacar potenciales `h`, se repesa cada arista
+/// como `w + h(u) - h(v)` (ya no hay negativos) y se corre Dijkstra desde cada nodo.
+///
+/// Devuelve lo mismo que [`floyd_warshall`]. Si hay un ciclo negativo es el error de
+/// [`bellman_ford_detallado`].
+pub fn johnson<W: Peso>(g: &DiGraph<String, W>) -> Result<TablaPares<W>, FalloBellmanFord> {
+    let n = g.node_count();
+    let desborde = |u: usize, v: usize| {
+        FalloBellmanFord::Desborde(format!(
+            "Desborde al repesar la arista {} -> {}, no cabe en el tipo de peso",
+            u, v
+        ))
+    };
+
+    // nodo virtual con aristas de peso 0 a todos, así todos quedan alcanzables
+    let mut aux = g.clone();
+    let s = aux.add_node(String::new());
+    for v in 0..n {
+        aux.add_edge(s, petgraph::prelude::NodeIndex::new(v), W::cero());
+    }
+    let (h, _, _) = bellman_ford_detallado(&aux, s.index())?;
+    let h: Vec<W> = h[..n].iter().map(|d| d.unwrap_or(W::cero())).collect();
+
+    let mut repesado = DiGraph::<String, W>::with_capacity(n, g.edge_count());
+    for l in g.node_weights() {
+        repesado.add_node(l.clone());
+    }
+    for e in g.edge_references() {
+        let (u, v) = (e.source().index(), e.target().index());
+        let w = e
+            .weight()
+            .sumar(h[u])
+            .and_then(|x| x.restar(h[v]))
+            .ok_or_else(|| desborde(u, v))?;
+        // con enteros nunca queda negativo; con Real el redondeo puede dar -1e-16
+        let w = w.max(W::cero());
+        repesado.add_edge(e.source(), e.target(), w);
+    }
+
+    let mut dist: Vec<Vec<Option<W>>> = vec![vec![None; n]; n];
+    let mut siguiente: Vec<Vec<Option<usize>>> = vec![vec![None; n]; n];
+    for u in 0..n {
+        let (d, preds, _) = dijkstra_eventos(&repesado, u).map_err(FalloBellmanFord::Desborde)?;
+        for v in 0..n {
+            if let Some(dv) = d[v] {
+                let real = dv
+                    .restar(h[u])
+                    .and_then(|x| x.sumar(h[v]))
+                    .ok_or_else(|| desborde(u, v))?;
+                dist[u][v] = Some(real);
+            }
+        }
+        siguiente[u] = primeros_saltos(&preds, &d, u);
+    }
+    Ok((dist, siguiente))
+}
+
+/// Reconstruye un camino mínimo de `origen` a `destino` con la matriz `siguiente` de
+/// [`floyd_warshall`] o [`johnson`]. `None` si no hay camino.
+pub fn camino_siguiente(
+    siguiente: &[Vec<Option<usize>>],
+    origen: usize,
+    destino: usize,
+) -> Option<Vec<usize>> {
+    siguiente[origen][destino]?;
+    let mut camino = vec![origen];
+    let mut x = origen;
+    while x != destino {
+        x = siguiente[x][destino]?;
+        camino.push(x);
+        // no debería pasar, pero así una matriz mal armada no cuelga el programa
+        if camino.len() > siguiente.len() {
+            return None;
+        }
+    }
+    Some(camino)
+}
+
+// Para cada v alcanzable, el primer nodo después de `origen` siguiendo el primer predecesor.
+// Se memoiza, así cada nodo se recorre una sola vez
+fn primeros_saltos<W>(
+    preds: &[Vec<usize>],
+    dist: &[Option<W>],
+    origen: usize,
+) -> Vec<Option<usize>> {
+    let n = preds.len();
+    let mut sig: Vec<Option<usize>> = vec![None; n];
+    sig[origen] = Some(origen);
+    for v in 0..n {
+        if dist[v].is_none() || sig[v].is_some() {
+            continue;
+        }
+        let mut pendientes = vec![];
+        let mut x = v;
+        while sig[x].is_none() {
+            pendientes.push(x);
+            x = preds[x][0];
+        }
+        let primero = if x == origen {
+            *pendientes.last().unwrap()
+        } else {
+            sig[x].unwrap()
+        };
+        for y in pendientes {
+            sig[y] = Some(primero);
+        }
+    }
+    sig
+}
+
+// Floyd-Warshall sabe que `i` está en un ciclo negativo pero no cuál es, Bellman-Ford desde `i`
+// lo encuentra
+fn ciclo_desde<W: Peso>(g: &DiGraph<String, W>, i: usize) -> FalloBellmanFord {
+    match bellman_ford_detallado(g, i) {
+        Err(e) => e,
+        Ok(_) => unreachable!("dist[{}][{}] < 0 implica un ciclo negativo", i, i),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::costo_camino;
+    use crate::grafo::de_texto;
+
+    // con negativos, una paralela y E sin aristas de salida
+    const NEGATIVOS: &str = "nodos: A B C D E\nA B 4\nA C 2\nC B -3\nB D 1\nD A 5\nA B 1\nD E 2";
+
+    #[test]
+    fn floyd_y_johnson_coinciden_con_bellman_ford() {
+        let g = de_texto::<i64>(NEGATIVOS);
+        let fw = floyd_warshall(&g).unwrap();
+        let jo = johnson(&g).unwrap();
+        for u in 0..5 {
+            let (dist, _, _) = bellman_ford_detallado(&g, u).unwrap();
+            assert_eq!(fw.0[u], dist);
+            assert_eq!(jo.0[u], dist);
+        }
+        assert_eq!(fw.0[0][1], Some(-1));
+        assert_eq!(fw.0[4][0], None);
+    }
+
+    #[test]
+    fn los_caminos_cuestan_la_distancia() {
+        let g = de_texto::<i64>(NEGATIVOS);
+        for (dist, siguiente) in [floyd_warshall(&g).unwrap(), johnson(&g).unwrap()] {
+            for (u, fila) in dist.iter().enumerate() {
+                for (v, d) in fila.iter().enumerate() {
+                    let camino = camino_siguiente(&siguiente, u, v);
+                    match *d {
+                        Some(d) => assert_eq!(costo_camino(&g, &camino.unwrap()), Ok(d)),
+                        None => assert_eq!(camino, None),
+                    }
+                }
+            }
+            assert_eq!(camino_siguiente(&siguiente, 0, 3), Some(vec![0, 2, 1, 3]));
+        }
+    }
+
+    #[test]
+    fn reportan_el_ciclo_negativo() {
+        let g = de_texto::<i64>("A B 1\nB C -2\nC B 1");
+        assert!(matches!(
+            floyd_warshall(&g),
+            Err(FalloBellmanFord::CicloNegativo { .. })
+        ));
+        assert!(matches!(
+            johnson(&g),
+            Err(FalloBellmanFord::CicloNegativo { .. })
+        ));
+    }
+}
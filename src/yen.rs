@@ -0,0 +1,135 @@
+use crate::dijkstra::{costo_camino, dijkstra_interno, iter_caminos};
+use crate::peso::Peso;
+use petgraph::graph::DiGraph;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+// Yen: los k caminos simples más cortos aunque no empaten. Cada camino nuevo sale de desviarse
+// ("spur") en algún nodo del anterior, corriendo Dijkstra sin los nodos de la raíz y sin las
+// aristas que ya usaron los caminos con esa misma raíz.
+
+/// Los `k` caminos simples (sin nodos repetidos) más cortos de `origen` a `destino`, ordenados
+/// por costo y luego por la secuencia de nodos. Cada uno va con su suma de pesos.
+///
+/// Devuelve menos de `k` si no hay tantos caminos distintos, y ninguno si `destino` no es
+/// alcanzable. Con aristas paralelas se cuenta la más barata entre cada par de nodos. Igual que
+/// [`dijkstra_detallado`](crate::dijkstra::dijkstra_detallado) devuelve `Err` con pesos
+/// negativos o si una suma se desborda.
+pub fn k_caminos_mas_cortos<W: Peso>(
+    g: &DiGraph<String, W>,
+    origen: usize,
+    destino: usize,
+    k: usize,
+) -> Result<Vec<(Vec<usize>, W)>, String> {
+    let mut a: Vec<(Vec<usize>, W)> = vec![];
+    if k == 0 {
+        return Ok(a);
+    }
+    let Some(primero) = desvio(g, origen, destino, &|_, _| true)? else {
+        return Ok(a);
+    };
+    let costo = costo_camino(g, &primero)?;
+    a.push((primero, costo));
+
+    // candidatos ordenados por (costo, camino), el Reverse lo vuelve min-heap
+    let mut b: BinaryHeap<Reverse<(W, Vec<usize>)>> = BinaryHeap::new();
+    let mut vistos: HashSet<Vec<usize>> = HashSet::new();
+    vistos.insert(a[0].0.clone());
+
+    while a.len() < k {
+        let anterior = a.last().unwrap().0.clone();
+        for i in 0..anterior.len() - 1 {
+            let spur = anterior[i];
+            let raiz = &anterior[..=i];
+
+            // se prohíben las aristas que siguen a esta raíz en los caminos ya aceptados
+            let aristas: HashSet<(usize, usize)> = a
+                .iter()
+                .filter(|(c, _)| c.len() > i + 1 && &c[..=i] == raiz)
+                .map(|(c, _)| (c[i], c[i + 1]))
+                .collect();
+            // y los nodos de la raíz, menos el spur, para que el camino siga siendo simple
+            let nodos: HashSet<usize> = raiz[..i].iter().copied().collect();
+            let permitida = |u: usize, v: usize| !nodos.contains(&v) && !aristas.contains(&(u, v));
+
+            let Some(resto) = desvio(g, spur, destino, &permitida)? else {
+                continue;
+            };
+            let mut camino = raiz[..i].to_vec();
+            camino.extend(resto);
+            if vistos.insert(camino.clone()) {
+                let costo = costo_camino(g, &camino)?;
+                b.push(Reverse((costo, camino)));
+            }
+        }
+        let Some(Reverse((costo, camino))) = b.pop() else {
+            break;
+        };
+        a.push((camino, costo));
+    }
+    // el primero sale de dijkstra, que entre empatados no elige por la secuencia de nodos
+    a.sort_by(|x, y| (&x.1, &x.0).cmp(&(&y.1, &y.0)));
+    Ok(a)
+}
+
+// Un camino mínimo de `desde` a `destino` respetando `permitida`, el primero de los empatados
+fn desvio<W: Peso>(
+    g: &DiGraph<String, W>,
+    desde: usize,
+    destino: usize,
+    permitida: &dyn Fn(usize, usize) -> bool,
+) -> Result<Option<Vec<usize>>, String> {
+    let (dist, preds, _, _) = dijkstra_interno(g, &[(desde, W::cero())], Some(destino), permitida)?;
+    if dist[destino].is_none() {
+        return Ok(None);
+    }
+    Ok(iter_caminos(&preds, desde, destino).next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grafo::de_texto;
+
+    #[test]
+    fn todos_los_caminos_simples_en_orden() {
+        // el ejemplo clásico de Yen: C=0 D=1 E=2 F=3 G=4 H=5, 7 caminos simples de C a H
+        let g = de_texto::<i64>("C D 3\nC E 2\nD F 4\nE D 1\nE F 2\nE G 3\nF G 2\nF H 1\nG H 2");
+        let caminos = k_caminos_mas_cortos(&g, 0, 5, 10).unwrap();
+        assert_eq!(
+            caminos,
+            vec![
+                (vec![0, 2, 3, 5], 5),
+                (vec![0, 2, 4, 5], 7),
+                (vec![0, 1, 3, 5], 8),
+                (vec![0, 2, 1, 3, 5], 8),
+                (vec![0, 2, 3, 4, 5], 8),
+                (vec![0, 1, 3, 4, 5], 11),
+                (vec![0, 2, 1, 3, 4, 5], 11),
+            ]
+        );
+        assert_eq!(k_caminos_mas_cortos(&g, 0, 5, 3).unwrap(), caminos[..3]);
+    }
+
+    #[test]
+    fn empates_por_secuencia_de_nodos() {
+        // dijkstra da primero A C D, pero a igual costo va antes A B D
+        let g = de_texto::<i64>("A B 1\nA C 1\nB D 1\nC D 1");
+        let caminos = k_caminos_mas_cortos(&g, 0, 3, 5).unwrap();
+        assert_eq!(caminos, vec![(vec![0, 1, 3], 2), (vec![0, 2, 3], 2)]);
+    }
+
+    #[test]
+    fn paralelas_no_cuentan_como_otro_camino() {
+        let g = de_texto::<i64>("A B 5\nA B 1\nB C 1\nA C 3");
+        let caminos = k_caminos_mas_cortos(&g, 0, 2, 5).unwrap();
+        assert_eq!(caminos, vec![(vec![0, 1, 2], 2), (vec![0, 2], 3)]);
+    }
+
+    #[test]
+    fn sin_caminos() {
+        let g = de_texto::<i64>("A B 1\nC D 1");
+        assert!(k_caminos_mas_cortos(&g, 0, 3, 3).unwrap().is_empty());
+        assert!(k_caminos_mas_cortos(&g, 0, 1, 0).unwrap().is_empty());
+    }
+}